@@ -0,0 +1,381 @@
+use std::iter;
+
+use crate::frequency::{checked_resulting_frequency, parse_changes, Frequency, FrequencyError};
+use crate::modular::checked_first_repeated_frequency;
+use crate::{AoC, Day01Solution};
+
+/// Overflow-safe solution, generic over the frequency type.
+///
+/// Parsing errors, out-of-range frequencies and inputs where no frequency
+/// repeats are all reported as errors instead of panicking or wrapping.
+#[derive(Debug)]
+pub struct Day01Checked<F = Day01Solution> {
+    changes: Result<Vec<F>, FrequencyError>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Day01CheckedError {
+    Frequency(FrequencyError),
+    NeverRepeats,
+}
+
+impl From<FrequencyError> for Day01CheckedError {
+    fn from(e: FrequencyError) -> Day01CheckedError {
+        Day01CheckedError::Frequency(e)
+    }
+}
+
+impl<'a, F: Frequency> AoC<'a> for Day01Checked<F> {
+    type Solution = Result<F, Day01CheckedError>;
+    type Data = Result<F, FrequencyError>;
+
+    fn description(&self) -> &'static str {
+        "Checked arithmetic, generic frequency type"
+    }
+
+    fn new(input: &'a str) -> Day01Checked<F> {
+        Day01Checked {
+            changes: parse_changes(input),
+        }
+    }
+
+    /// The parsed changes, or the parsing error as the only item.
+    fn parsed<'b>(&'b self) -> Box<dyn Iterator<Item = Self::Data> + 'b> {
+        match self.changes {
+            Ok(ref changes) => Box::new(changes.iter().cloned().map(Ok)),
+            Err(ref e) => Box::new(iter::once(Err(e.clone()))),
+        }
+    }
+
+    fn solution_part1(&self) -> Self::Solution {
+        let changes = self.changes.as_ref().map_err(Clone::clone)?;
+        Ok(checked_resulting_frequency(changes)?)
+    }
+
+    fn solution_part2(&self) -> Self::Solution {
+        let changes = self.changes.as_ref().map_err(Clone::clone)?;
+        checked_first_repeated_frequency(changes)?.ok_or(Day01CheckedError::NeverRepeats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod aoc2018 {
+        mod day01 {
+            use crate::frequency::FrequencyError;
+            use crate::{AoC, Day01Checked};
+
+            #[test]
+            fn parse() {
+                let parsed: Result<Vec<_>, _> = Day01Checked::<i64>::new("+1, -2, +3, +1")
+                    .parsed()
+                    .collect();
+                assert_eq!(parsed, Ok(vec![1, -2, 3, 1]));
+
+                let parsed: Result<Vec<_>, _> =
+                    Day01Checked::<i8>::new("+1, +128").parsed().collect();
+                assert_eq!(
+                    parsed,
+                    Err(FrequencyError::Parse {
+                        index: 1,
+                        token: "+128".to_string()
+                    })
+                );
+            }
+
+            mod part1 {
+
+                mod solution {
+                    use crate::tests::init_logger;
+                    use crate::{AoC, Day01Checked, PUZZLE_INPUT};
+
+                    #[test]
+                    fn solution() {
+                        init_logger();
+
+                        let expected = Ok(408);
+                        let to_check = Day01Checked::<i64>::new(PUZZLE_INPUT).solution_part1();
+
+                        assert_eq!(expected, to_check);
+                    }
+                }
+
+                mod given {
+                    use crate::tests::init_logger;
+                    use crate::{AoC, Day01Checked};
+
+                    #[test]
+                    fn ex01() {
+                        init_logger();
+
+                        let expected = Ok(3);
+                        let input = "+1, -2, +3, +1";
+                        let to_check = Day01Checked::<i8>::new(input).solution_part1();
+
+                        assert_eq!(expected, to_check);
+                    }
+                }
+
+                mod extra {
+                    use crate::frequency::FrequencyError;
+                    use crate::tests::init_logger;
+                    use crate::{AoC, Day01Checked, Day01CheckedError};
+
+                    #[test]
+                    fn overflow() {
+                        init_logger();
+
+                        let input = "+100, +27, +1, -50";
+                        assert_eq!(
+                            Day01Checked::<i8>::new(input).solution_part1(),
+                            Err(Day01CheckedError::Frequency(FrequencyError::Overflow {
+                                index: 2
+                            }))
+                        );
+                        assert_eq!(Day01Checked::<i16>::new(input).solution_part1(), Ok(78));
+                    }
+
+                    #[test]
+                    fn out_of_range() {
+                        init_logger();
+
+                        let input = "+9223372036854775807, +9223372036854775807";
+                        assert_eq!(
+                            Day01Checked::<i64>::new(input).solution_part1(),
+                            Err(Day01CheckedError::Frequency(FrequencyError::Overflow {
+                                index: 1
+                            }))
+                        );
+                        assert_eq!(
+                            Day01Checked::<i128>::new(input).solution_part1(),
+                            Ok(2 * i128::from(i64::MAX))
+                        );
+
+                        let input = "+1, +9223372036854775808";
+                        assert_eq!(
+                            Day01Checked::<i64>::new(input).solution_part1(),
+                            Err(Day01CheckedError::Frequency(FrequencyError::Parse {
+                                index: 1,
+                                token: "+9223372036854775808".to_string()
+                            }))
+                        );
+                    }
+
+                    #[cfg(feature = "bigint")]
+                    #[test]
+                    fn bigint() {
+                        use num_bigint::BigInt;
+
+                        init_logger();
+
+                        let input = "+170141183460469231731687303715884105727, +1, -2";
+                        let expected: BigInt =
+                            "170141183460469231731687303715884105726".parse().unwrap();
+                        assert_eq!(
+                            Day01Checked::<BigInt>::new(input).solution_part1(),
+                            Ok(expected)
+                        );
+                    }
+                }
+            }
+
+            mod part2 {
+
+                mod solution {
+                    use crate::tests::init_logger;
+                    use crate::{AoC, Day01Checked, PUZZLE_INPUT};
+
+                    #[test]
+                    fn solution() {
+                        init_logger();
+
+                        let expected = Ok(55250);
+                        let to_check = Day01Checked::<i64>::new(PUZZLE_INPUT).solution_part2();
+
+                        assert_eq!(expected, to_check);
+                    }
+                }
+
+                mod given {
+                    use crate::tests::init_logger;
+                    use crate::{AoC, Day01Checked};
+
+                    #[test]
+                    fn ex00() {
+                        init_logger();
+
+                        let expected = Ok(2);
+                        let input = "+1, -2, +3, +1";
+                        let to_check = Day01Checked::<i8>::new(input).solution_part2();
+
+                        assert_eq!(expected, to_check);
+                    }
+
+                    #[test]
+                    fn ex03() {
+                        init_logger();
+
+                        let expected = Ok(5);
+                        let input = "-6, +3, +8, +5, -6";
+                        let to_check = Day01Checked::<i32>::new(input).solution_part2();
+
+                        assert_eq!(expected, to_check);
+                    }
+
+                    #[test]
+                    fn ex04() {
+                        init_logger();
+
+                        let expected = Ok(14);
+                        let input = "+7, +7, -2, -7, -4";
+                        let to_check = Day01Checked::<i64>::new(input).solution_part2();
+
+                        assert_eq!(expected, to_check);
+                    }
+                }
+
+                mod extra {
+                    use std::collections::HashSet;
+
+                    use crate::frequency::FrequencyError;
+                    use crate::tests::init_logger;
+                    use crate::{AoC, Day01Checked, Day01CheckedError};
+
+                    #[test]
+                    fn never_repeats() {
+                        init_logger();
+
+                        assert_eq!(
+                            Day01Checked::<i64>::new("+1, +1").solution_part2(),
+                            Err(Day01CheckedError::NeverRepeats)
+                        );
+                    }
+
+                    #[test]
+                    fn overflow() {
+                        init_logger();
+
+                        // 0, 100, then 1, 101, then 2, 102, ... 100 is reached again after
+                        // 100 passes, going through 199 which does not fit an `i8`.
+                        let input = "+100, -99";
+                        assert_eq!(
+                            Day01Checked::<i8>::new(input).solution_part2(),
+                            Err(Day01CheckedError::Frequency(FrequencyError::Overflow {
+                                index: 0
+                            }))
+                        );
+                        assert_eq!(Day01Checked::<i16>::new(input).solution_part2(), Ok(100));
+
+                        // Same, mirrored
+                        let input = "-100, +99";
+                        assert!(Day01Checked::<i8>::new(input).solution_part2().is_err());
+                        assert_eq!(Day01Checked::<i16>::new(input).solution_part2(), Ok(-100));
+                    }
+
+                    #[test]
+                    fn fits_until_repeat() {
+                        init_logger();
+
+                        // 0, 1, 127, then 1 again: the next frequency of the second pass
+                        // (128) would not fit an `i8`, but is never reached.
+                        let input = "+1, +126, -126";
+                        assert_eq!(Day01Checked::<i8>::new(input).solution_part2(), Ok(1));
+                        let input = "+120, -119";
+                        assert!(Day01Checked::<i8>::new(input).solution_part2().is_err());
+                    }
+
+                    #[test]
+                    fn min_max_boundaries() {
+                        init_logger();
+
+                        // Negative drift: 0, -127, -128, then -1, -128
+                        let input = "-127, -1, +127";
+                        assert_eq!(Day01Checked::<i8>::new(input).solution_part2(), Ok(-128));
+                        // Positive drift: 0, 126, 127, then 1, 127
+                        let input = "+126, +1, -126";
+                        assert_eq!(Day01Checked::<i8>::new(input).solution_part2(), Ok(127));
+                        // Drift of `i8::MIN`: 0, 100, -28, then -128, -28
+                        let input = "+100, -128, -100";
+                        assert_eq!(Day01Checked::<i8>::new(input).solution_part2(), Ok(-28));
+                        // Drift of `i8::MAX`: 0, -100, 27, then 127, 27
+                        let input = "-100, +127, +100";
+                        assert_eq!(Day01Checked::<i8>::new(input).solution_part2(), Ok(27));
+                        // One step too far in either direction
+                        let input = "-127, -1, -1, +128";
+                        assert!(Day01Checked::<i8>::new(input).solution_part2().is_err());
+                        let input = "+127, +1";
+                        assert!(Day01Checked::<i8>::new(input).solution_part2().is_err());
+                    }
+
+                    /// Simulate the device, with the same overflow checks
+                    fn brute_force(changes: &[i8]) -> Result<i8, Day01CheckedError> {
+                        let mut seen = HashSet::new();
+                        let mut frequency: i8 = 0;
+                        seen.insert(frequency);
+                        // Any non-zero drift overflows within 256 passes
+                        for (index, change) in
+                            changes.iter().enumerate().cycle().take(changes.len() * 257)
+                        {
+                            frequency = frequency.checked_add(*change).ok_or(
+                                Day01CheckedError::Frequency(FrequencyError::Overflow { index }),
+                            )?;
+                            if !seen.insert(frequency) {
+                                return Ok(frequency);
+                            }
+                        }
+                        Err(Day01CheckedError::NeverRepeats)
+                    }
+
+                    #[test]
+                    fn match_brute_force_full_range() {
+                        init_logger();
+
+                        // Deterministic pseudo-random inputs over the whole `i8` range
+                        let mut seed: u64 = 0x2018_1201;
+                        for _ in 0..20_000 {
+                            let len = 1 + (seed % 5) as usize;
+                            let changes: Vec<i8> = (0..len)
+                                .map(|_| {
+                                    seed = seed
+                                        .wrapping_mul(6_364_136_223_846_793_005)
+                                        .wrapping_add(1_442_695_040_888_963_407);
+                                    // Smaller changes too, for repeats after more passes
+                                    ((seed >> 56) as u8 as i8) >> (seed % 4)
+                                })
+                                .collect();
+                            let input: Vec<String> =
+                                changes.iter().map(|change| change.to_string()).collect();
+                            let to_check =
+                                Day01Checked::<i8>::new(&input.join(", ")).solution_part2();
+                            match brute_force(&changes) {
+                                Ok(expected) => {
+                                    assert_eq!(to_check, Ok(expected), "changes: {:?}", changes)
+                                }
+                                // Both overflow, or the repeat would only come after one
+                                Err(_) => assert!(to_check.is_err(), "changes: {:?}", changes),
+                            }
+                        }
+                    }
+
+                    #[cfg(feature = "bigint")]
+                    #[test]
+                    fn bigint() {
+                        use num_bigint::BigInt;
+
+                        init_logger();
+
+                        let input = "+170141183460469231731687303715884105727, -170141183460469231731687303715884105726";
+                        let expected: BigInt =
+                            "170141183460469231731687303715884105727".parse().unwrap();
+                        assert_eq!(
+                            Day01Checked::<BigInt>::new(input).solution_part2(),
+                            Ok(expected)
+                        );
+                        assert!(Day01Checked::<i128>::new(input).solution_part2().is_err());
+                    }
+                }
+            }
+        }
+    }
+}
@@ -9,7 +9,7 @@ pub struct Day01BuildIter<'a> {
 
 impl<'a> AoC<'a> for Day01BuildIter<'a> {
     type Solution = Day01Solution;
-    type Data = Day01Data<'a>;
+    type Data = Day01Data;
 
     fn description(&self) -> &'static str {
         "Parse string dynamically"
@@ -19,7 +19,7 @@ impl<'a> AoC<'a> for Day01BuildIter<'a> {
         Day01BuildIter { input }
     }
 
-    fn parsed(&self) -> Self::Data {
+    fn parsed<'b>(&'b self) -> Box<dyn Iterator<Item = Self::Data> + 'b> {
         Box::new(parse_input(self.input))
     }
 
@@ -0,0 +1,122 @@
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+use num_integer::Integer;
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, Signed};
+
+/// Integer type usable to represent frequencies.
+///
+/// Implemented for all signed primitive integers and, with the `bigint`
+/// feature, for `num_bigint::BigInt`. All arithmetic done through this trait
+/// is checked: going out of the type's range is reported as a
+/// [`FrequencyError::Overflow`] instead of wrapping.
+///
+/// `Day01Checked` and `checked_first_repeated_frequency()` are generic over
+/// it. The simulating implementations stay on `i64`: they are the benchmark
+/// baselines and neither check for overflows nor stop on inputs that never
+/// repeat.
+pub trait Frequency:
+    Integer + Signed + CheckedAdd + CheckedSub + CheckedMul + Clone + Hash + Debug + FromStr
+{
+}
+
+impl<T> Frequency for T where
+    T: Integer + Signed + CheckedAdd + CheckedSub + CheckedMul + Clone + Hash + Debug + FromStr
+{
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrequencyError {
+    /// Frequency change number `index` is not a valid integer, or does not
+    /// fit the frequency type.
+    Parse { index: usize, token: String },
+    /// Applying frequency change number `index` takes the frequency out of
+    /// the frequency type's range.
+    Overflow { index: usize },
+}
+
+impl Display for FrequencyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FrequencyError::Parse { index, token } => {
+                write!(f, "Can't parse frequency change #{}: {:?}", index, token)
+            }
+            FrequencyError::Overflow { index } => write!(
+                f,
+                "Frequency overflow when applying frequency change #{}",
+                index
+            ),
+        }
+    }
+}
+
+impl Error for FrequencyError {}
+
+/// Parse all frequency changes of `input` as `F`.
+///
+/// Contrary to `parse_input()`, invalid or out-of-range changes are reported
+/// instead of skipped.
+pub fn parse_changes<F: Frequency>(input: &str) -> Result<Vec<F>, FrequencyError> {
+    input
+        .split(&[',', '\n'][..])
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .enumerate()
+        .map(|(index, token)| {
+            // `BigInt` does not accept an explicit plus sign
+            let digits = token.strip_prefix('+').unwrap_or(token);
+            digits.parse().map_err(|_| FrequencyError::Parse {
+                index,
+                token: token.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Frequency reached after applying all `changes` once, starting from zero.
+pub fn checked_resulting_frequency<F: Frequency>(changes: &[F]) -> Result<F, FrequencyError> {
+    changes
+        .iter()
+        .enumerate()
+        .try_fold(F::zero(), |frequency, (index, change)| {
+            frequency
+                .checked_add(change)
+                .ok_or(FrequencyError::Overflow { index })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frequency::{checked_resulting_frequency, parse_changes, FrequencyError};
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_changes::<i8>("+1, -2, +3, +1"), Ok(vec![1, -2, 3, 1]));
+        assert_eq!(
+            parse_changes::<i8>("+1\n+128\n"),
+            Err(FrequencyError::Parse {
+                index: 1,
+                token: "+128".to_string()
+            })
+        );
+        assert_eq!(
+            parse_changes::<i64>("+1, x"),
+            Err(FrequencyError::Parse {
+                index: 1,
+                token: "x".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn resulting_frequency() {
+        assert_eq!(checked_resulting_frequency::<i8>(&[100, 27]), Ok(127));
+        assert_eq!(
+            checked_resulting_frequency::<i8>(&[100, 27, 1, -50]),
+            Err(FrequencyError::Overflow { index: 2 })
+        );
+        assert_eq!(checked_resulting_frequency::<i128>(&[]), Ok(0));
+    }
+}
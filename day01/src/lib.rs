@@ -88,6 +88,11 @@
 // #[macro_use]
 // extern crate log;
 
+#[cfg(feature = "bigint")]
+extern crate num_bigint;
+extern crate num_integer;
+extern crate num_traits;
+
 use std::fmt::Debug;
 
 mod dynparse;
@@ -99,8 +104,19 @@ pub use preparse_hashmap::Day01PreParseHashMap;
 mod preparse_hashset;
 pub use preparse_hashset::Day01PreParseHashSet;
 
+pub mod modular;
+pub use modular::Day01Modular;
+
+pub mod stream;
+
+pub mod frequency;
+
+mod checked;
+pub use checked::{Day01Checked, Day01CheckedError};
+
 pub trait AoC<'a>: Debug {
     type Solution;
+    /// Type of the parsed frequency changes
     type Data;
 
     fn description(&self) -> &'static str {
@@ -111,7 +127,8 @@ pub trait AoC<'a>: Debug {
     where
         Self: Sized;
 
-    fn parsed(&self) -> Self::Data;
+    /// Frequency changes, borrowed from `self` when already parsed.
+    fn parsed<'b>(&'b self) -> Box<dyn Iterator<Item = Self::Data> + 'b>;
 
     fn solution_part1(&self) -> Self::Solution {
         unimplemented!()
@@ -135,7 +152,7 @@ fn parse_input<'a>(input: &'a str) -> impl Iterator<Item = i64> + 'a {
 }
 
 type Day01Solution = i64;
-type Day01Data<'a> = Box<Iterator<Item = Day01Solution> + 'a>;
+type Day01Data = Day01Solution;
 
 static PUZZLE_INPUT: &str = include_str!("../input");
 
@@ -144,13 +161,14 @@ pub mod benchmark {
 
     pub type ToBenchmark<'a> = Day01BuildIter<'a>;
     pub type BenchmarkVector<'a> =
-        Vec<Box<dyn AoC<'a, Solution = Day01Solution, Data = Day01Data<'a>> + 'a>>;
+        Vec<Box<dyn AoC<'a, Solution = Day01Solution, Data = Day01Data> + 'a>>;
 
     pub fn to_benchmark<'a>() -> BenchmarkVector<'a> {
         vec![
             Box::new(Day01BuildIter::new(PUZZLE_INPUT)),
             Box::new(Day01PreParseHashMap::new(PUZZLE_INPUT)),
             Box::new(Day01PreParseHashSet::new(PUZZLE_INPUT)),
+            Box::new(Day01Modular::new(PUZZLE_INPUT)),
         ]
     }
 }
@@ -160,7 +178,8 @@ mod tests {
     extern crate env_logger;
     use std::env;
 
-    use crate::parse_input;
+    use crate::benchmark::to_benchmark;
+    use crate::{parse_input, PUZZLE_INPUT};
 
     pub fn init_logger() {
         env::var("RUST_LOG")
@@ -179,4 +198,15 @@ mod tests {
         let parsed: Vec<_> = parse_input("+1, -2, +3, +1").collect();
         assert_eq!(parsed, vec![1, -2, 3, 1]);
     }
+
+    #[test]
+    fn parsed_all_implementations() {
+        init_logger();
+
+        let expected: Vec<_> = parse_input(PUZZLE_INPUT).collect();
+        for implementation in &to_benchmark() {
+            let parsed: Vec<_> = implementation.parsed().collect();
+            assert_eq!(expected, parsed, "{}", implementation.description());
+        }
+    }
 }
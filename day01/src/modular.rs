@@ -0,0 +1,376 @@
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use crate::frequency::{Frequency, FrequencyError};
+use crate::{parse_input, AoC, Day01Data, Day01Solution};
+
+#[derive(Debug)]
+pub struct Day01Modular {
+    changes: Vec<i64>,
+}
+
+impl Day01Modular {
+    /// First frequency reached twice, or `None` if no frequency ever repeats.
+    pub fn first_repeat(&self) -> Option<Day01Solution> {
+        first_repeated_frequency(&self.changes)
+    }
+}
+
+impl<'a> AoC<'a> for Day01Modular {
+    type Solution = Day01Solution;
+    type Data = Day01Data;
+
+    fn description(&self) -> &'static str {
+        "Modular arithmetic"
+    }
+
+    fn new(input: &'a str) -> Day01Modular {
+        Day01Modular {
+            changes: parse_input(input).collect(),
+        }
+    }
+
+    fn parsed<'b>(&'b self) -> Box<dyn Iterator<Item = Self::Data> + 'b> {
+        Box::new(self.changes.iter().cloned())
+    }
+
+    fn solution_part1(&self) -> Self::Solution {
+        self.changes.iter().sum()
+    }
+
+    fn solution_part2(&self) -> Self::Solution {
+        self.first_repeat().expect("Frequencies never repeat")
+    }
+}
+
+/// Find the first frequency reached twice when `changes` is applied over and
+/// over, without simulating the passes.
+///
+/// Returns `None` when no frequency is ever reached twice. See
+/// [`checked_first_repeated_frequency()`] for the algorithm.
+///
+/// # Panics
+///
+/// If a frequency reached before the repeat does not fit in an `i64`.
+pub fn first_repeated_frequency(changes: &[i64]) -> Option<i64> {
+    checked_first_repeated_frequency(changes).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Find the first frequency reached twice when `changes` is applied over and
+/// over, without simulating the passes.
+///
+/// Let `f_i` be the frequency reached after the first `i` changes (`f_0 = 0`)
+/// and `drift` the total change of one pass. At pass `k` the device visits
+/// `f_i + k * drift`. Two frequencies can only meet if they are congruent
+/// modulo `drift`, and within such a class, `f_i` first reaches the next
+/// larger `f_j` (the next smaller one for a negative drift) after
+/// `(f_j - f_i) / drift` passes.
+/// Sorting each class is enough to find the earliest repeat, giving
+/// `O(n log n)` in the number of changes.
+///
+/// Returns `Ok(None)` when no frequency is ever reached twice, and an error
+/// if any frequency reached before the repeat does not fit in `F`.
+pub fn checked_first_repeated_frequency<F: Frequency>(
+    changes: &[F],
+) -> Result<Option<F>, FrequencyError> {
+    let n = changes.len();
+    if n == 0 {
+        return Ok(None);
+    }
+    // Index of the change leading to `f_i`
+    let change_index = |i: usize| (i + n - 1) % n;
+
+    // Frequencies reached during the first pass, before applying the last change.
+    // A repeat within the first pass always comes before any later one, and
+    // before any overflow later in the pass.
+    let mut frequencies = Vec::with_capacity(n);
+    let mut seen = HashSet::with_capacity(n);
+    let mut frequency = F::zero();
+    for (index, change) in changes.iter().enumerate() {
+        if !seen.insert(frequency.clone()) {
+            return Ok(Some(frequency));
+        }
+        let next = frequency
+            .checked_add(change)
+            .ok_or(FrequencyError::Overflow { index })?;
+        frequencies.push(mem::replace(&mut frequency, next));
+    }
+    let drift = frequency;
+    if drift.is_zero() {
+        // Back to the starting frequency at the end of the first pass
+        return Ok(Some(F::zero()));
+    }
+
+    // Work with frequencies going up; with a negative drift, mirror them with
+    // `-1 - f`, which contrary to `-f` maps `F::MIN` and `F::MAX` onto each
+    // other without overflowing. `|drift|` may not fit in `F` either, so
+    // `drift` is kept as is: congruence classes and quotients modulo `drift`
+    // are the same as modulo `|drift|`, up to the sign of the quotients.
+    let negative = drift.is_negative();
+    let mirrored: Vec<F> = frequencies
+        .iter()
+        .map(|frequency| {
+            if negative {
+                -F::one() - frequency.clone()
+            } else {
+                frequency.clone()
+            }
+        })
+        .collect();
+
+    let mut classes: HashMap<F, Vec<(&F, usize)>> = HashMap::new();
+    for (idx, frequency) in mirrored.iter().enumerate() {
+        classes
+            .entry(frequency.mod_floor(&drift))
+            .or_default()
+            .push((frequency, idx));
+    }
+
+    // A drift of one has no distinct classes and `F::MIN / -1` overflows,
+    // otherwise quotients are at most half the range of `F` apart.
+    let unit_drift = drift.is_one() || drift == -F::one();
+    // Earliest repeat as (passes, index within the pass, index of the repeated frequency)
+    let mut earliest: Option<(F, usize, usize)> = None;
+    for class in classes.values_mut() {
+        class.sort_unstable();
+        for pair in class.windows(2) {
+            let (from, from_idx) = pair[0];
+            let (to, to_idx) = pair[1];
+            let signed_passes = if unit_drift {
+                to.checked_sub(from).map(|gap| gap / drift.clone())
+            } else {
+                to.div_floor(&drift).checked_sub(&from.div_floor(&drift))
+            };
+            let passes = signed_passes
+                .ok_or(FrequencyError::Overflow {
+                    index: change_index(to_idx),
+                })?
+                .abs();
+            let is_earlier = match earliest {
+                Some((ref current, current_idx, _)) => (&passes, from_idx) < (current, current_idx),
+                None => true,
+            };
+            if is_earlier {
+                earliest = Some((passes, from_idx, to_idx));
+            }
+        }
+    }
+
+    let (passes, repeat_idx, to_idx) = match earliest {
+        Some(earliest) => earliest,
+        None => return Ok(None),
+    };
+
+    // Every frequency visited before the repeat must fit in `F`: the full
+    // pass before the repeat, then the start of the repeating pass.
+    let previous_passes = passes.clone() - F::one();
+    for (idx, frequency) in frequencies.iter().enumerate() {
+        let passes = if idx <= repeat_idx {
+            &passes
+        } else {
+            &previous_passes
+        };
+        drift
+            .checked_mul(passes)
+            .and_then(|offset| frequency.checked_add(&offset))
+            .ok_or(FrequencyError::Overflow {
+                index: change_index(idx),
+            })?;
+    }
+
+    Ok(Some(frequencies[to_idx].clone()))
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod aoc2018 {
+        mod day01 {
+            use crate::{AoC, Day01Modular};
+
+            #[test]
+            fn parse() {
+                let parsed: Vec<_> = Day01Modular::new("+1, -2, +3, +1").parsed().collect();
+                assert_eq!(parsed, vec![1, -2, 3, 1]);
+            }
+
+            mod part1 {
+
+                mod solution {
+                    use crate::tests::init_logger;
+                    use crate::{AoC, Day01Modular, PUZZLE_INPUT};
+
+                    #[test]
+                    fn solution() {
+                        init_logger();
+
+                        let expected = 408;
+                        let to_check = Day01Modular::new(PUZZLE_INPUT).solution_part1();
+
+                        assert_eq!(expected, to_check);
+                    }
+                }
+
+                mod given {
+                    use crate::tests::init_logger;
+                    use crate::{AoC, Day01Modular};
+
+                    #[test]
+                    fn ex01() {
+                        init_logger();
+
+                        let expected = 3;
+                        let input = "+1, -2, +3, +1";
+                        let to_check = Day01Modular::new(input).solution_part1();
+
+                        assert_eq!(expected, to_check);
+                    }
+                }
+            }
+
+            mod part2 {
+
+                mod solution {
+                    use crate::tests::init_logger;
+                    use crate::{AoC, Day01Modular, PUZZLE_INPUT};
+
+                    #[test]
+                    fn solution() {
+                        init_logger();
+
+                        let expected = 55250;
+                        let to_check = Day01Modular::new(PUZZLE_INPUT).solution_part2();
+
+                        assert_eq!(expected, to_check);
+                    }
+                }
+
+                mod given {
+                    use crate::tests::init_logger;
+                    use crate::{AoC, Day01Modular};
+
+                    #[test]
+                    fn ex00() {
+                        init_logger();
+
+                        let expected = 2;
+                        let input = "+1, -2, +3, +1";
+                        let to_check = Day01Modular::new(input).solution_part2();
+
+                        assert_eq!(expected, to_check);
+                    }
+
+                    #[test]
+                    fn ex01() {
+                        init_logger();
+
+                        let expected = 0;
+                        let input = "+1, -1";
+                        let to_check = Day01Modular::new(input).solution_part2();
+
+                        assert_eq!(expected, to_check);
+                    }
+
+                    #[test]
+                    fn ex02() {
+                        init_logger();
+
+                        let expected = 10;
+                        let input = "+3, +3, +4, -2, -4";
+                        let to_check = Day01Modular::new(input).solution_part2();
+
+                        assert_eq!(expected, to_check);
+                    }
+
+                    #[test]
+                    fn ex03() {
+                        init_logger();
+
+                        let expected = 5;
+                        let input = "-6, +3, +8, +5, -6";
+                        let to_check = Day01Modular::new(input).solution_part2();
+
+                        assert_eq!(expected, to_check);
+                    }
+
+                    #[test]
+                    fn ex04() {
+                        init_logger();
+
+                        let expected = 14;
+                        let input = "+7, +7, -2, -7, -4";
+                        let to_check = Day01Modular::new(input).solution_part2();
+
+                        assert_eq!(expected, to_check);
+                    }
+                }
+
+                mod extra {
+                    use std::collections::HashSet;
+
+                    use crate::modular::first_repeated_frequency;
+                    use crate::tests::init_logger;
+                    use crate::{AoC, Day01Modular};
+
+                    fn brute_force(changes: &[i64], max_passes: usize) -> Option<i64> {
+                        let mut seen = HashSet::new();
+                        let mut frequency = 0;
+                        seen.insert(frequency);
+                        for change in changes.iter().cycle().take(changes.len() * max_passes) {
+                            frequency += change;
+                            if !seen.insert(frequency) {
+                                return Some(frequency);
+                            }
+                        }
+                        None
+                    }
+
+                    #[test]
+                    fn never_repeats() {
+                        init_logger();
+
+                        assert_eq!(Day01Modular::new("+1, +1").first_repeat(), None);
+                        assert_eq!(Day01Modular::new("-3, +1, -5").first_repeat(), None);
+                        assert_eq!(Day01Modular::new("").first_repeat(), None);
+                    }
+
+                    #[test]
+                    fn negative_drift() {
+                        init_logger();
+
+                        // 0, -1, 1, then -2, -3, -1 in the second pass
+                        assert_eq!(first_repeated_frequency(&[-1, 2, -3]), Some(-1));
+                        assert_eq!(first_repeated_frequency(&[-7, -7, 2, 7, 4]), Some(-14));
+                    }
+
+                    #[test]
+                    fn match_brute_force() {
+                        init_logger();
+
+                        // Deterministic pseudo-random inputs
+                        let mut seed: u64 = 0x2018_1201;
+                        for _ in 0..500 {
+                            let len = 1 + (seed % 7) as usize;
+                            let changes: Vec<i64> = (0..len)
+                                .map(|_| {
+                                    seed = seed
+                                        .wrapping_mul(6_364_136_223_846_793_005)
+                                        .wrapping_add(1_442_695_040_888_963_407);
+                                    ((seed >> 33) % 21) as i64 - 10
+                                })
+                                .collect();
+                            let expected = brute_force(&changes, 50);
+                            let to_check = first_repeated_frequency(&changes);
+                            if expected.is_some() {
+                                assert_eq!(to_check, expected, "changes: {:?}", changes);
+                            } else if let Some(to_check) = to_check {
+                                // Repeat exists but only after more passes
+                                assert_eq!(brute_force(&changes, 10_000), Some(to_check));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
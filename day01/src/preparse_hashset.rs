@@ -9,8 +9,7 @@ pub struct Day01PreParseHashSet {
 
 impl<'a> AoC<'a> for Day01PreParseHashSet {
     type Solution = Day01Solution;
-    type Data = Day01Data<'a>;
-    // type Data = Box<Iterator<Item = Day01Solution> + 'a>;
+    type Data = Day01Data;
 
     fn description(&self) -> &'static str {
         "Pre-Parse string HashSet"
@@ -22,12 +21,8 @@ impl<'a> AoC<'a> for Day01PreParseHashSet {
         }
     }
 
-    // fn parsed(&self) -> Self::Data {
-    fn parsed(&self) -> Box<Iterator<Item = i64> + 'a> {
-        // let i = self.input.iter().cloned();
-        // let d = Box::new(i);
-        // d
-        unimplemented!()
+    fn parsed<'b>(&'b self) -> Box<dyn Iterator<Item = Self::Data> + 'b> {
+        Box::new(self.input.iter().cloned())
     }
 
     fn solution_part1(&self) -> Self::Solution {
@@ -64,7 +59,6 @@ mod tests {
             use crate::{AoC, Day01PreParseHashSet};
 
             #[test]
-            #[ignore]
             fn parse() {
                 let parsed: Vec<_> = Day01PreParseHashSet::new("+1, -2, +3, +1")
                     .parsed()
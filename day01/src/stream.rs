@@ -0,0 +1,348 @@
+//! Streaming frequency analysis over any [`BufRead`].
+//!
+//! Frequency changes are tokenized straight from the reader's buffer, so
+//! [`changes()`], [`running_frequencies()`] and [`frequency_stats()`] run in
+//! constant memory whatever the size of the input. [`repeat_stats()`] needs to
+//! remember the frequencies it has seen, so its memory grows with the number
+//! of _distinct_ frequencies, not with the size of the input.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Seek, SeekFrom};
+use std::str;
+
+/// Longest accepted frequency change token, in bytes (sign and padding included).
+const MAX_TOKEN_LEN: usize = 64;
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Iterator over the frequency changes read from a [`BufRead`].
+///
+/// Changes are separated by commas and/or newlines, like the puzzle input.
+#[derive(Debug)]
+pub struct Changes<R> {
+    reader: R,
+    token: Vec<u8>,
+    done: bool,
+}
+
+/// Read frequency changes incrementally from `reader`.
+pub fn changes<R: BufRead>(reader: R) -> Changes<R> {
+    Changes {
+        reader,
+        token: Vec::with_capacity(MAX_TOKEN_LEN),
+        done: false,
+    }
+}
+
+impl<R: BufRead> Changes<R> {
+    /// Parse and clear the pending token, `None` if it is blank.
+    fn take_token(&mut self) -> Option<io::Result<i64>> {
+        let parsed = match str::from_utf8(&self.token) {
+            Ok(token) if token.trim().is_empty() => None,
+            Ok(token) => Some(
+                token
+                    .trim()
+                    .parse()
+                    .map_err(|e| invalid_data(format!("Can't parse {:?}: {}", token.trim(), e))),
+            ),
+            Err(e) => Some(Err(invalid_data(format!(
+                "Invalid frequency change: {}",
+                e
+            )))),
+        };
+        self.token.clear();
+        parsed
+    }
+}
+
+impl<R: BufRead> Iterator for Changes<R> {
+    type Item = io::Result<i64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let (separator_found, used) = {
+                let buf = match self.reader.fill_buf() {
+                    Ok(buf) => buf,
+                    Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => {
+                        self.done = true;
+                        return Some(Err(e));
+                    }
+                };
+                if buf.is_empty() {
+                    self.done = true;
+                    (true, 0)
+                } else {
+                    match buf.iter().position(|&b| b == b',' || b == b'\n') {
+                        Some(pos) => {
+                            self.token.extend_from_slice(&buf[..pos]);
+                            (true, pos + 1)
+                        }
+                        None => {
+                            self.token.extend_from_slice(buf);
+                            (false, buf.len())
+                        }
+                    }
+                }
+            };
+            self.reader.consume(used);
+
+            if self.token.len() > MAX_TOKEN_LEN {
+                self.done = true;
+                return Some(Err(invalid_data(format!(
+                    "Frequency change longer than {} bytes",
+                    MAX_TOKEN_LEN
+                ))));
+            }
+
+            if separator_found {
+                if let Some(change) = self.take_token() {
+                    return Some(change);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Iterator over the frequency reached after each change, starting from zero.
+#[derive(Debug)]
+pub struct RunningFrequencies<R> {
+    changes: Changes<R>,
+    frequency: i64,
+}
+
+/// Read frequency changes from `reader` and yield the running frequency.
+///
+/// The starting frequency (zero) is not yielded.
+pub fn running_frequencies<R: BufRead>(reader: R) -> RunningFrequencies<R> {
+    RunningFrequencies {
+        changes: changes(reader),
+        frequency: 0,
+    }
+}
+
+impl<R: BufRead> Iterator for RunningFrequencies<R> {
+    type Item = io::Result<i64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let change = match self.changes.next()? {
+            Ok(change) => change,
+            Err(e) => return Some(Err(e)),
+        };
+        self.frequency = match self.frequency.checked_add(change) {
+            Some(frequency) => frequency,
+            None => return Some(Err(invalid_data("Frequency overflow".to_string()))),
+        };
+        Some(Ok(self.frequency))
+    }
+}
+
+/// Statistics of a single pass over the frequency changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyStats {
+    /// Number of frequency changes in one pass
+    pub nb_changes: usize,
+    /// Frequency at the end of the pass (the part 1 answer)
+    pub final_frequency: i64,
+    /// Lowest frequency reached, including the starting zero
+    pub min: i64,
+    /// Highest frequency reached, including the starting zero
+    pub max: i64,
+}
+
+/// Compute [`FrequencyStats`] in a single streaming pass over `reader`.
+pub fn frequency_stats<R: BufRead>(reader: R) -> io::Result<FrequencyStats> {
+    let mut stats = FrequencyStats {
+        nb_changes: 0,
+        final_frequency: 0,
+        min: 0,
+        max: 0,
+    };
+    for frequency in running_frequencies(reader) {
+        let frequency = frequency?;
+        stats.nb_changes += 1;
+        stats.final_frequency = frequency;
+        stats.min = stats.min.min(frequency);
+        stats.max = stats.max.max(frequency);
+    }
+    Ok(stats)
+}
+
+/// First frequency reached twice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FirstRepeat {
+    /// The repeated frequency (the part 2 answer)
+    pub frequency: i64,
+    /// Number of complete passes over the changes before the repeat
+    pub passes_before: usize,
+}
+
+/// Repetition statistics over several passes of the frequency changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepeatStats {
+    /// Number of passes performed
+    pub nb_passes: usize,
+    /// First frequency reached twice, if any within the passes performed
+    pub first_repeat: Option<FirstRepeat>,
+    /// Every frequency reached exactly twice, in increasing order. The
+    /// starting zero counts as one visit.
+    pub seen_twice: Vec<i64>,
+}
+
+/// Run `nb_passes` passes over the frequency changes, rewinding `reader`
+/// between passes, and collect [`RepeatStats`].
+///
+/// Passes are read from the reader's current position. If the changes
+/// drift to zero, every frequency repeats on each pass; the passes are
+/// still all performed.
+pub fn repeat_stats<R: BufRead + Seek>(mut reader: R, nb_passes: usize) -> io::Result<RepeatStats> {
+    let start = reader.stream_position()?;
+
+    // Visit count per frequency, saturating at 3 since only "exactly twice" matters
+    let mut visits: HashMap<i64, u8> = HashMap::new();
+    visits.insert(0, 1);
+
+    let mut first_repeat = None;
+    let mut frequency: i64 = 0;
+
+    for pass in 0..nb_passes {
+        reader.seek(SeekFrom::Start(start))?;
+        for change in changes(&mut reader) {
+            frequency = frequency
+                .checked_add(change?)
+                .ok_or_else(|| invalid_data("Frequency overflow".to_string()))?;
+            let count = visits.entry(frequency).or_insert(0);
+            *count = (*count + 1).min(3);
+            if *count == 2 && first_repeat.is_none() {
+                first_repeat = Some(FirstRepeat {
+                    frequency,
+                    passes_before: pass,
+                });
+            }
+        }
+    }
+
+    let mut seen_twice: Vec<i64> = visits
+        .into_iter()
+        .filter(|(_frequency, count)| *count == 2)
+        .map(|(frequency, _count)| frequency)
+        .collect();
+    seen_twice.sort_unstable();
+
+    Ok(RepeatStats {
+        nb_passes,
+        first_repeat,
+        seen_twice,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Cursor};
+
+    use crate::stream::{
+        changes, frequency_stats, repeat_stats, running_frequencies, FirstRepeat, FrequencyStats,
+    };
+    use crate::tests::init_logger;
+    use crate::PUZZLE_INPUT;
+
+    #[test]
+    fn parse() {
+        init_logger();
+
+        let parsed: Vec<i64> = changes("+1, -2, +3, +1".as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(parsed, vec![1, -2, 3, 1]);
+
+        let parsed: Vec<i64> = changes("+1\n-2\n\n+3\n+1\n".as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(parsed, vec![1, -2, 3, 1]);
+    }
+
+    #[test]
+    fn parse_small_buffer() {
+        init_logger();
+
+        // Tokens straddle the reader's internal buffer boundaries
+        let reader = BufReader::with_capacity(2, "+100, -20,\n+3".as_bytes());
+        let parsed: Vec<i64> = changes(reader).collect::<Result<_, _>>().unwrap();
+        assert_eq!(parsed, vec![100, -20, 3]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        init_logger();
+
+        let mut parsed = changes("+1, x2, +3".as_bytes());
+        assert_eq!(parsed.next().unwrap().unwrap(), 1);
+        assert!(parsed.next().unwrap().is_err());
+
+        let too_long = format!("+1, {}", "1".repeat(100));
+        assert!(changes(too_long.as_bytes()).any(|change| change.is_err()));
+    }
+
+    #[test]
+    fn running() {
+        init_logger();
+
+        let frequencies: Vec<i64> = running_frequencies("+1, -2, +3, +1".as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(frequencies, vec![1, -1, 2, 3]);
+    }
+
+    #[test]
+    fn stats() {
+        init_logger();
+
+        assert_eq!(
+            frequency_stats("+1, -2, +3, +1".as_bytes()).unwrap(),
+            FrequencyStats {
+                nb_changes: 4,
+                final_frequency: 3,
+                min: -1,
+                max: 3,
+            }
+        );
+
+        let stats = frequency_stats(PUZZLE_INPUT.as_bytes()).unwrap();
+        assert_eq!(stats.final_frequency, 408);
+    }
+
+    #[test]
+    fn repeats() {
+        init_logger();
+
+        // 0, 1, -1, 2, 3, then 4, 2, 5, 6, then 7, 5, 8, 9
+        let stats = repeat_stats(Cursor::new("+1, -2, +3, +1"), 3).unwrap();
+        assert_eq!(stats.nb_passes, 3);
+        assert_eq!(
+            stats.first_repeat,
+            Some(FirstRepeat {
+                frequency: 2,
+                passes_before: 1,
+            })
+        );
+        assert_eq!(stats.seen_twice, vec![2, 5]);
+
+        let stats = repeat_stats(Cursor::new("+1, +1"), 10).unwrap();
+        assert_eq!(stats.first_repeat, None);
+        assert!(stats.seen_twice.is_empty());
+    }
+
+    #[test]
+    fn repeats_puzzle() {
+        init_logger();
+
+        let stats = repeat_stats(Cursor::new(PUZZLE_INPUT), 200).unwrap();
+        assert_eq!(
+            stats.first_repeat.map(|repeat| repeat.frequency),
+            Some(55250)
+        );
+    }
+}
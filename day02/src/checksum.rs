@@ -0,0 +1,104 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Letter-frequency checksum of a list of box IDs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checksum {
+    /// For each requested multiplicity `m`, number of IDs containing at
+    /// least one character appearing exactly `m` times.
+    pub counts: BTreeMap<usize, usize>,
+}
+
+impl Checksum {
+    /// Product of the per-multiplicity counts.
+    pub fn product(&self) -> usize {
+        self.counts.values().product()
+    }
+}
+
+/// Compute the checksum of `ids` for the given `multiplicities`.
+///
+/// Characters are Unicode scalar values, so IDs are not restricted to ASCII.
+/// The puzzle's checksum is the product for multiplicities `{2, 3}`.
+pub fn checksum<'a>(ids: impl IntoIterator<Item = &'a str>, multiplicities: &[usize]) -> Checksum {
+    let mut counts: BTreeMap<usize, usize> = multiplicities.iter().map(|m| (*m, 0)).collect();
+
+    let mut seen: HashMap<char, usize> = HashMap::new();
+    for id in ids {
+        seen.clear();
+        for c in id.chars() {
+            *seen.entry(c).or_insert(0) += 1;
+        }
+        let id_multiplicities: HashSet<usize> = seen.values().cloned().collect();
+        for (multiplicity, count) in counts.iter_mut() {
+            if id_multiplicities.contains(multiplicity) {
+                *count += 1;
+            }
+        }
+    }
+
+    Checksum { counts }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::checksum::checksum;
+    use crate::tests::init_logger;
+    use crate::{parse_input, PUZZLE_INPUT};
+
+    #[test]
+    fn given() {
+        init_logger();
+
+        let input = "abcdef
+                     bababc
+                     abbcde
+                     abcccd
+                     aabcdd
+                     abcdee
+                     ababab";
+        let to_check = checksum(parse_input(input), &[2, 3]);
+        let expected: BTreeMap<_, _> = vec![(2, 4), (3, 3)].into_iter().collect();
+        assert_eq!(to_check.counts, expected);
+        assert_eq!(to_check.product(), 12);
+    }
+
+    #[test]
+    fn solution() {
+        init_logger();
+
+        assert_eq!(checksum(parse_input(PUZZLE_INPUT), &[2, 3]).product(), 5000);
+    }
+
+    #[test]
+    fn other_multiplicities() {
+        init_logger();
+
+        let input = "abcdef
+                     bababc
+                     abbcde
+                     abcccd
+                     aabcdd
+                     abcdee
+                     ababab";
+        let to_check = checksum(parse_input(input), &[1, 2, 3, 4]);
+        let expected: BTreeMap<_, _> = vec![(1, 6), (2, 4), (3, 3), (4, 0)].into_iter().collect();
+        assert_eq!(to_check.counts, expected);
+        assert_eq!(to_check.product(), 0);
+
+        assert_eq!(checksum(parse_input(input), &[]).product(), 1);
+    }
+
+    #[test]
+    fn unicode() {
+        init_logger();
+
+        // 'é' and 'e' are different characters, each taking more than a byte
+        // in the first two IDs.
+        let ids = vec!["éléphant", "été", "eée", "🎄🎄x"];
+        let to_check = checksum(ids, &[2, 3]);
+        let expected: BTreeMap<_, _> = vec![(2, 4), (3, 0)].into_iter().collect();
+        assert_eq!(to_check.counts, expected);
+    }
+}
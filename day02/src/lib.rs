@@ -90,6 +90,13 @@ pub use initial::Day02Initial;
 pub mod bound_check_elision;
 pub use bound_check_elision::Day02BoundCheckElision;
 
+pub mod wildcard_hash;
+pub use wildcard_hash::Day02WildcardHash;
+
+pub mod similarity;
+
+pub mod checksum;
+
 pub trait AoC<'a>: Debug {
     type Solution1;
     type Solution2;
@@ -140,6 +147,7 @@ pub mod benchmark {
         vec![
             Box::new(Day02Initial::new(PUZZLE_INPUT)),
             Box::new(Day02BoundCheckElision::new(PUZZLE_INPUT)),
+            Box::new(Day02WildcardHash::new(PUZZLE_INPUT)),
         ]
     }
 }
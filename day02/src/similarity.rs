@@ -0,0 +1,319 @@
+//! Near-duplicate search over box IDs.
+//!
+//! IDs are stored in a [BK-tree](https://en.wikipedia.org/wiki/BK-tree): each
+//! node's children are keyed by their distance to the node, and the triangle
+//! inequality lets a query for IDs within distance `k` of `q` skip every
+//! subtree whose key is outside `d(q, node) ± k`. For small `k` only a small
+//! fraction of the IDs is ever compared.
+
+use strsim::levenshtein;
+
+/// Edit distance between two IDs, counted in Unicode scalar values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Number of positions holding different characters. Positions past the
+    /// end of the shorter ID count as different, so IDs of different lengths
+    /// are at least their length difference apart.
+    Hamming,
+    /// Minimum number of single character insertions, deletions or
+    /// substitutions.
+    Levenshtein,
+}
+
+impl Metric {
+    pub fn distance(self, a: &str, b: &str) -> usize {
+        match self {
+            Metric::Hamming => {
+                let mut a = a.chars();
+                let mut b = b.chars();
+                let mut distance = 0;
+                loop {
+                    match (a.next(), b.next()) {
+                        (None, None) => return distance,
+                        (Some(c1), Some(c2)) if c1 == c2 => {}
+                        _ => distance += 1,
+                    }
+                }
+            }
+            Metric::Levenshtein => levenshtein(a, b),
+        }
+    }
+}
+
+/// Two IDs within the requested distance of each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimilarPair<'a> {
+    /// Index of the first ID, in insertion order
+    pub first: usize,
+    /// Index of the second ID, always greater than `first`
+    pub second: usize,
+    pub first_id: &'a str,
+    pub second_id: &'a str,
+    pub distance: usize,
+}
+
+#[derive(Debug)]
+struct Node {
+    /// Indices of the (identical) IDs stored in this node
+    ids: Vec<usize>,
+    /// Child nodes, with their distance to this node
+    children: Vec<(usize, usize)>,
+}
+
+/// Index of box IDs answering "which IDs are within distance `k`" queries.
+#[derive(Debug)]
+pub struct SimilarityIndex<'a> {
+    metric: Metric,
+    ids: Vec<&'a str>,
+    nodes: Vec<Node>,
+}
+
+impl<'a> SimilarityIndex<'a> {
+    pub fn new(ids: impl IntoIterator<Item = &'a str>, metric: Metric) -> SimilarityIndex<'a> {
+        let mut index = SimilarityIndex {
+            metric,
+            ids: Vec::new(),
+            nodes: Vec::new(),
+        };
+        for id in ids {
+            index.insert(id);
+        }
+        index
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    pub fn ids(&self) -> &[&'a str] {
+        &self.ids
+    }
+
+    /// Add `id` to the index, returning its index.
+    pub fn insert(&mut self, id: &'a str) -> usize {
+        let idx = self.ids.len();
+        self.ids.push(id);
+
+        if self.nodes.is_empty() {
+            self.nodes.push(Node {
+                ids: vec![idx],
+                children: Vec::new(),
+            });
+            return idx;
+        }
+
+        let mut node = 0;
+        loop {
+            let distance = self.metric.distance(id, self.ids[self.nodes[node].ids[0]]);
+            if distance == 0 {
+                self.nodes[node].ids.push(idx);
+                return idx;
+            }
+            let child = self.nodes[node]
+                .children
+                .iter()
+                .find(|(child_distance, _)| *child_distance == distance)
+                .map(|(_, child)| *child);
+            match child {
+                Some(child) => node = child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node {
+                        ids: vec![idx],
+                        children: Vec::new(),
+                    });
+                    self.nodes[node].children.push((distance, child));
+                    return idx;
+                }
+            }
+        }
+    }
+
+    /// Indices and distances of all stored IDs within distance `k` of `query`,
+    /// sorted by index.
+    pub fn within(&self, query: &str, k: usize) -> Vec<(usize, usize)> {
+        let mut found = Vec::new();
+        let mut to_visit = if self.nodes.is_empty() {
+            Vec::new()
+        } else {
+            vec![0]
+        };
+        while let Some(node) = to_visit.pop() {
+            let node = &self.nodes[node];
+            let distance = self.metric.distance(query, self.ids[node.ids[0]]);
+            if distance <= k {
+                found.extend(node.ids.iter().map(|idx| (*idx, distance)));
+            }
+            let range = distance.saturating_sub(k)..=distance + k;
+            to_visit.extend(
+                node.children
+                    .iter()
+                    .filter(|(child_distance, _)| range.contains(child_distance))
+                    .map(|(_, child)| *child),
+            );
+        }
+        found.sort_unstable();
+        found
+    }
+
+    /// All pairs of stored IDs within distance `k` of each other, sorted by
+    /// `(first, second)`.
+    pub fn pairs_within(&self, k: usize) -> Vec<SimilarPair<'a>> {
+        let mut pairs = Vec::new();
+        for (first, first_id) in self.ids.iter().enumerate() {
+            for (second, distance) in self.within(first_id, k) {
+                if second > first {
+                    pairs.push(SimilarPair {
+                        first,
+                        second,
+                        first_id,
+                        second_id: self.ids[second],
+                        distance,
+                    });
+                }
+            }
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::similarity::{Metric, SimilarPair, SimilarityIndex};
+    use crate::tests::init_logger;
+    use crate::{parse_input, PUZZLE_INPUT};
+
+    fn brute_force(ids: &[&str], metric: Metric, k: usize) -> Vec<(usize, usize, usize)> {
+        let mut pairs = Vec::new();
+        for i in 0..ids.len() {
+            for j in i + 1..ids.len() {
+                let distance = metric.distance(ids[i], ids[j]);
+                if distance <= k {
+                    pairs.push((i, j, distance));
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(Metric::Hamming.distance("fghij", "fguij"), 1);
+        assert_eq!(Metric::Hamming.distance("abc", "abcde"), 2);
+        assert_eq!(Metric::Hamming.distance("abc", "bc"), 3);
+        assert_eq!(Metric::Hamming.distance("héllo", "hello"), 1);
+        assert_eq!(Metric::Levenshtein.distance("abc", "bc"), 1);
+        assert_eq!(Metric::Levenshtein.distance("héllo", "hello"), 1);
+    }
+
+    #[test]
+    fn given() {
+        init_logger();
+
+        let input = "abcde
+                     fghij
+                     klmno
+                     pqrst
+                     fguij
+                     axcye
+                     wvxyz";
+        let index = SimilarityIndex::new(parse_input(input), Metric::Hamming);
+        assert_eq!(
+            index.pairs_within(1),
+            vec![SimilarPair {
+                first: 1,
+                second: 4,
+                first_id: "fghij",
+                second_id: "fguij",
+                distance: 1,
+            }]
+        );
+        assert_eq!(
+            index.pairs_within(2).len(),
+            brute_force(index.ids(), Metric::Hamming, 2).len()
+        );
+    }
+
+    #[test]
+    fn solution() {
+        init_logger();
+
+        let index = SimilarityIndex::new(parse_input(PUZZLE_INPUT), Metric::Hamming);
+        let pairs = index.pairs_within(1);
+        assert_eq!(pairs.len(), 1);
+
+        let common: String = pairs[0]
+            .first_id
+            .chars()
+            .zip(pairs[0].second_id.chars())
+            .filter_map(|(c1, c2)| if c1 == c2 { Some(c1) } else { None })
+            .collect();
+        assert_eq!(common, "ymdrchgpvwfloluktajxijsqb");
+    }
+
+    #[test]
+    fn different_lengths() {
+        init_logger();
+
+        let ids = ["abcd", "abc", "abd", "xabcd", "abcd"];
+        let index = SimilarityIndex::new(ids.iter().cloned(), Metric::Levenshtein);
+        let pairs: Vec<_> = index
+            .pairs_within(1)
+            .into_iter()
+            .map(|pair| (pair.first, pair.second, pair.distance))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (0, 1, 1),
+                (0, 2, 1),
+                (0, 3, 1),
+                (0, 4, 0),
+                (1, 2, 1),
+                (1, 4, 1),
+                (2, 4, 1),
+                (3, 4, 1),
+            ]
+        );
+        assert_eq!(index.within("zabcd", 1), vec![(0, 1), (3, 1), (4, 1)]);
+    }
+
+    #[test]
+    fn match_brute_force() {
+        init_logger();
+
+        // Deterministic pseudo-random IDs over a small alphabet, of varying lengths
+        let mut seed: u64 = 0x2018_1202;
+        let mut next = move |modulo: u64| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) % modulo
+        };
+        let ids: Vec<String> = (0..300)
+            .map(|_| {
+                let len = 3 + next(4);
+                (0..len).map(|_| (b'a' + next(3) as u8) as char).collect()
+            })
+            .collect();
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+        for metric in &[Metric::Hamming, Metric::Levenshtein] {
+            let index = SimilarityIndex::new(ids.iter().cloned(), *metric);
+            for k in 0..3 {
+                let pairs: Vec<_> = index
+                    .pairs_within(k)
+                    .into_iter()
+                    .map(|pair| (pair.first, pair.second, pair.distance))
+                    .collect();
+                assert_eq!(
+                    pairs,
+                    brute_force(&ids, *metric, k),
+                    "{:?}, k={}",
+                    metric,
+                    k
+                );
+            }
+        }
+    }
+}
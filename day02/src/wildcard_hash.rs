@@ -0,0 +1,275 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::checksum::checksum;
+use crate::{parse_input, AoC, Day02Parsed, Day02SolutionPart1, Day02SolutionPart2};
+
+/// Base of the polynomial rolling hash (odd, so multiplication is invertible modulo 2^64)
+const HASH_BASE: u64 = 0x100_0000_01b3;
+
+#[derive(Debug)]
+pub struct Day02WildcardHash<'a> {
+    lines: Vec<&'a str>,
+}
+
+/// The two box IDs differing by exactly one character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchingIds<'a> {
+    /// Line index of the first ID in the input
+    pub first_line: usize,
+    /// Line index of the second ID in the input
+    pub second_line: usize,
+    pub first: &'a str,
+    pub second: &'a str,
+    /// Position, in characters, of the only differing character
+    pub position: usize,
+    /// Characters common to both IDs
+    pub common: String,
+}
+
+impl<'a> Day02WildcardHash<'a> {
+    /// Find the first pair of IDs differing at exactly one position.
+    ///
+    /// Every ID is hashed once per position with that position masked, using
+    /// prefix and suffix rolling hashes so that each masked hash is computed in
+    /// constant time. Two IDs differing only at position `p` share the same
+    /// masked hash for `p`; candidates are verified to rule out hash collisions.
+    ///
+    /// Repeated IDs are skipped: distinct IDs sharing a masked hash match, so
+    /// barring collisions each masked hash holds a single line until a match is
+    /// found. This runs in `O(n·L)` expected time for `n` IDs of length `L`.
+    pub fn find_matching_ids(&self) -> Option<MatchingIds<'a>> {
+        // (masked position, length, prefix hash, suffix hash) -> line indices
+        let mut masked: HashMap<(usize, usize, u64, u64), Vec<usize>> = HashMap::new();
+        let mut seen = HashSet::new();
+        let chars: Vec<Vec<char>> = self
+            .lines
+            .iter()
+            .map(|line| line.chars().collect())
+            .collect();
+
+        for (line, id) in chars.iter().enumerate() {
+            if !seen.insert(self.lines[line]) {
+                continue;
+            }
+            let len = id.len();
+
+            // prefix[i]: hash of id[..i]; suffix[i]: hash of id[i..]
+            let mut prefix = vec![0u64; len + 1];
+            let mut suffix = vec![0u64; len + 1];
+            for i in 0..len {
+                prefix[i + 1] = prefix[i]
+                    .wrapping_mul(HASH_BASE)
+                    .wrapping_add(u64::from(id[i]));
+                let j = len - 1 - i;
+                suffix[j] = suffix[j + 1]
+                    .wrapping_mul(HASH_BASE)
+                    .wrapping_add(u64::from(id[j]));
+            }
+
+            for position in 0..len {
+                let key = (position, len, prefix[position], suffix[position + 1]);
+                let candidates = masked.entry(key).or_default();
+                let found = candidates.iter().find(|&&other| {
+                    let other = &chars[other];
+                    other[position] != id[position]
+                        && other[..position] == id[..position]
+                        && other[position + 1..] == id[position + 1..]
+                });
+                if let Some(&first_line) = found {
+                    let common = id[..position]
+                        .iter()
+                        .chain(id[position + 1..].iter())
+                        .collect();
+                    return Some(MatchingIds {
+                        first_line,
+                        second_line: line,
+                        first: self.lines[first_line],
+                        second: self.lines[line],
+                        position,
+                        common,
+                    });
+                }
+                candidates.push(line);
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a> AoC<'a> for Day02WildcardHash<'a> {
+    type Solution1 = Day02SolutionPart1;
+    type Solution2 = Day02SolutionPart2;
+    type Parsed = Day02Parsed<'a>;
+
+    fn description(&self) -> &'static str {
+        "Wildcard position hashing"
+    }
+
+    fn new(input: &'a str) -> Day02WildcardHash<'a> {
+        Day02WildcardHash {
+            lines: parse_input(input).collect(),
+        }
+    }
+
+    fn solution_part1(&self) -> Self::Solution1 {
+        checksum(self.lines.iter().cloned(), &[2, 3]).product() as Day02SolutionPart1
+    }
+
+    fn solution_part2(&self) -> Self::Solution2 {
+        self.find_matching_ids().unwrap().common
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod part1 {
+        mod solution {
+            use super::super::super::Day02WildcardHash;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 5000;
+                let to_check = Day02WildcardHash::new(PUZZLE_INPUT).solution_part1();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day02WildcardHash;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = 12;
+                let input = "abcdef
+                             bababc
+                             abbcde
+                             abcccd
+                             aabcdd
+                             abcdee
+                             ababab";
+                let to_check = Day02WildcardHash::new(input).solution_part1();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+    }
+
+    mod part2 {
+
+        mod solution {
+            use super::super::super::Day02WildcardHash;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = "ymdrchgpvwfloluktajxijsqb";
+                let to_check = Day02WildcardHash::new(PUZZLE_INPUT).solution_part2();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+
+        mod given {
+            use super::super::super::{Day02WildcardHash, MatchingIds};
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = "fgij";
+                let input = "abcde
+                             fghij
+                             klmno
+                             pqrst
+                             fguij
+                             axcye
+                             wvxyz";
+                let to_check = Day02WildcardHash::new(input).solution_part2();
+
+                assert_eq!(expected, to_check);
+            }
+
+            #[test]
+            fn ex01_matching_ids() {
+                init_logger();
+
+                let input = "abcde
+                             fghij
+                             klmno
+                             pqrst
+                             fguij
+                             axcye
+                             wvxyz";
+                let to_check = Day02WildcardHash::new(input).find_matching_ids();
+
+                assert_eq!(
+                    to_check,
+                    Some(MatchingIds {
+                        first_line: 1,
+                        second_line: 4,
+                        first: "fghij",
+                        second: "fguij",
+                        position: 2,
+                        common: "fgij".to_string(),
+                    })
+                );
+            }
+        }
+
+        mod extra {
+            use super::super::super::Day02WildcardHash;
+            use crate::tests::init_logger;
+            use crate::AoC;
+
+            #[test]
+            fn no_match() {
+                init_logger();
+
+                // Identical IDs and IDs of different lengths never match
+                let input = "abcd
+                             abcd
+                             abc
+                             abcde
+                             wxyz";
+                assert_eq!(Day02WildcardHash::new(input).find_matching_ids(), None);
+            }
+
+            #[test]
+            fn repeated_ids() {
+                init_logger();
+
+                let input = "abcd
+                             abcd
+                             abcd
+                             abce";
+                let to_check = Day02WildcardHash::new(input).find_matching_ids().unwrap();
+                assert_eq!((to_check.first_line, to_check.second_line), (0, 3));
+                assert_eq!(to_check.common, "abc");
+            }
+
+            #[test]
+            fn unicode() {
+                init_logger();
+
+                let input = "héllo
+                             wörld
+                             hällo";
+                let to_check = Day02WildcardHash::new(input).find_matching_ids().unwrap();
+                assert_eq!(to_check.position, 1);
+                assert_eq!(to_check.common, "hllo");
+            }
+        }
+    }
+}
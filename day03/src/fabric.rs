@@ -0,0 +1,200 @@
+use std::collections::{BTreeSet, HashMap};
+use std::iter::FromIterator;
+
+use crate::Input;
+
+/// Mutable fabric, keeping the overlapped area and the intact claims up to
+/// date as claims are added and withdrawn.
+///
+/// Each update visits every square inch of the claim once, and every other
+/// claim on the same square inch, so its cost is proportional to the claim's
+/// size (times the local multiplicity).
+#[derive(Debug, Default)]
+pub struct Fabric {
+    claims: HashMap<usize, Input>,
+    /// IDs of the claims over each square inch
+    cells: HashMap<(usize, usize), Vec<usize>>,
+    /// Number of square inches each claim shares with at least one other claim
+    shared_cells: HashMap<usize, usize>,
+    overlapped_area: usize,
+    intact: BTreeSet<usize>,
+}
+
+impl Fabric {
+    pub fn new() -> Fabric {
+        Fabric::default()
+    }
+
+    /// Number of claims on the fabric.
+    pub fn len(&self) -> usize {
+        self.claims.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.claims.is_empty()
+    }
+
+    pub fn contains(&self, id: usize) -> bool {
+        self.claims.contains_key(&id)
+    }
+
+    /// Square inches claimed by two or more claims.
+    pub fn overlapped_area(&self) -> usize {
+        self.overlapped_area
+    }
+
+    /// IDs of the claims not overlapping any other claim, sorted.
+    pub fn intact_claims(&self) -> impl Iterator<Item = usize> + '_ {
+        self.intact.iter().cloned()
+    }
+
+    /// Add `claim` to the fabric.
+    ///
+    /// Returns `false`, leaving the fabric untouched, if a claim with the same
+    /// ID is already present.
+    pub fn insert(&mut self, claim: Input) -> bool {
+        let id = claim.id;
+        if self.claims.contains_key(&id) {
+            return false;
+        }
+
+        let mut shared = 0;
+        for coord in cells(&claim) {
+            let ids = self.cells.entry(coord).or_default();
+            if ids.len() == 1 {
+                // The square inch becomes overlapped
+                let other = ids[0];
+                self.overlapped_area += 1;
+                let other_shared = self.shared_cells.get_mut(&other).unwrap();
+                *other_shared += 1;
+                if *other_shared == 1 {
+                    self.intact.remove(&other);
+                }
+            }
+            if !ids.is_empty() {
+                shared += 1;
+            }
+            ids.push(id);
+        }
+
+        self.shared_cells.insert(id, shared);
+        if shared == 0 {
+            self.intact.insert(id);
+        }
+        self.claims.insert(id, claim);
+        true
+    }
+
+    /// Withdraw the claim `id` from the fabric, returning it if it was present.
+    pub fn remove(&mut self, id: usize) -> Option<Input> {
+        let claim = self.claims.remove(&id)?;
+
+        for coord in cells(&claim) {
+            let ids = self.cells.get_mut(&coord).unwrap();
+            let position = ids.iter().position(|&other| other == id).unwrap();
+            ids.swap_remove(position);
+            match ids.len() {
+                0 => {
+                    self.cells.remove(&coord);
+                }
+                1 => {
+                    // The square inch is not overlapped anymore
+                    let other = ids[0];
+                    self.overlapped_area -= 1;
+                    let other_shared = self.shared_cells.get_mut(&other).unwrap();
+                    *other_shared -= 1;
+                    if *other_shared == 0 {
+                        self.intact.insert(other);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.shared_cells.remove(&id);
+        self.intact.remove(&id);
+        Some(claim)
+    }
+}
+
+impl FromIterator<Input> for Fabric {
+    fn from_iter<I: IntoIterator<Item = Input>>(claims: I) -> Fabric {
+        let mut fabric = Fabric::new();
+        for claim in claims {
+            fabric.insert(claim);
+        }
+        fabric
+    }
+}
+
+/// Coordinates of all the square inches of `claim`.
+fn cells(claim: &Input) -> impl Iterator<Item = (usize, usize)> {
+    let (left, wide, top, tall) = (claim.left, claim.wide, claim.top, claim.tall);
+    (left..left + wide).flat_map(move |i| (top..top + tall).map(move |j| (i, j)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fabric::Fabric;
+    use crate::tests::init_logger;
+    use crate::{parse_input, AoC, Day03SweepLine, PUZZLE_INPUT};
+
+    #[test]
+    fn given() {
+        init_logger();
+
+        let input = "#1 @ 1,3: 4x4
+                     #2 @ 3,1: 4x4
+                     #3 @ 5,5: 2x2";
+        let mut fabric: Fabric = parse_input(input).collect();
+        assert_eq!(fabric.len(), 3);
+        assert_eq!(fabric.overlapped_area(), 4);
+        assert_eq!(fabric.intact_claims().collect::<Vec<_>>(), vec![3]);
+
+        let claim2 = fabric.remove(2).unwrap();
+        assert_eq!(fabric.overlapped_area(), 0);
+        assert_eq!(fabric.intact_claims().collect::<Vec<_>>(), vec![1, 3]);
+        assert!(fabric.remove(2).is_none());
+        assert!(!fabric.contains(2));
+
+        assert!(fabric.insert(claim2));
+        assert_eq!(fabric.overlapped_area(), 4);
+        assert_eq!(fabric.intact_claims().collect::<Vec<_>>(), vec![3]);
+
+        let duplicate = parse_input("#3 @ 0,0: 10x10").next().unwrap();
+        assert!(!fabric.insert(duplicate));
+        assert_eq!(fabric.overlapped_area(), 4);
+    }
+
+    #[test]
+    fn withdraw_one_at_a_time() {
+        init_logger();
+
+        let mut fabric: Fabric = parse_input(PUZZLE_INPUT).collect();
+        assert_eq!(fabric.overlapped_area(), 100595);
+        assert_eq!(fabric.intact_claims().collect::<Vec<_>>(), vec![415]);
+
+        // Withdraw every claim, checking against a full recomputation now and then
+        let mut remaining: Vec<String> = PUZZLE_INPUT.lines().map(String::from).collect();
+        let mut step = 0;
+        while let Some(line) = remaining.pop() {
+            let claim = parse_input(&line).next().unwrap();
+            assert!(fabric.remove(claim.id).is_some());
+
+            step += 1;
+            if step % 97 == 0 && !remaining.is_empty() {
+                let input = remaining.join("\n");
+                let expected = Day03SweepLine::new(&input);
+                assert_eq!(fabric.overlapped_area(), expected.solution_part1());
+                assert_eq!(
+                    fabric.intact_claims().collect::<Vec<_>>(),
+                    expected.intact_claims()
+                );
+            }
+        }
+
+        assert!(fabric.is_empty());
+        assert_eq!(fabric.overlapped_area(), 0);
+        assert_eq!(fabric.intact_claims().count(), 0);
+    }
+}
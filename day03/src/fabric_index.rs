@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use crate::Input;
+
+/// Side of the square tiles used to find the claims covering a point
+const TILE_SIZE: usize = 32;
+
+/// Rectangular region of the fabric, in square inches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub left: usize,
+    pub top: usize,
+    pub wide: usize,
+    pub tall: usize,
+}
+
+impl<'a> From<&'a Input> for Region {
+    fn from(claim: &'a Input) -> Region {
+        Region {
+            left: claim.left,
+            top: claim.top,
+            wide: claim.wide,
+            tall: claim.tall,
+        }
+    }
+}
+
+/// Queryable index of the claims over the fabric.
+///
+/// The fabric is kept dense, so its size is bounded by the claims' extent
+/// (see `Day03SweepLine` for claims too large for a dense grid). Building the
+/// index costs `O(width × height + n)` using a 2D difference array.
+#[derive(Debug)]
+pub struct FabricIndex {
+    width: usize,
+    height: usize,
+    /// Number of claims over each square inch, row-major
+    multiplicity: Vec<u32>,
+    claims: Vec<(usize, Region)>,
+    /// Indices into `claims` of the claims touching each tile
+    tiles: HashMap<(usize, usize), Vec<usize>>,
+}
+
+impl FabricIndex {
+    pub fn new(claims: impl IntoIterator<Item = Input>) -> FabricIndex {
+        let claims: Vec<(usize, Region)> = claims
+            .into_iter()
+            .map(|claim| (claim.id, Region::from(&claim)))
+            .collect();
+        let width = claims
+            .iter()
+            .map(|(_id, region)| region.left + region.wide)
+            .max()
+            .unwrap_or(0);
+        let height = claims
+            .iter()
+            .map(|(_id, region)| region.top + region.tall)
+            .max()
+            .unwrap_or(0);
+
+        // Difference array: +1 at each claim's top-left corner, -1 past its
+        // top-right and bottom-left corners, +1 past its bottom-right one.
+        let mut diff = vec![0i32; (width + 1) * (height + 1)];
+        let mut tiles: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (i, (_id, region)) in claims.iter().enumerate() {
+            if region.wide == 0 || region.tall == 0 {
+                continue;
+            }
+            let (x1, y1) = (region.left, region.top);
+            let (x2, y2) = (region.left + region.wide, region.top + region.tall);
+            diff[y1 * (width + 1) + x1] += 1;
+            diff[y1 * (width + 1) + x2] -= 1;
+            diff[y2 * (width + 1) + x1] -= 1;
+            diff[y2 * (width + 1) + x2] += 1;
+
+            for tile_y in y1 / TILE_SIZE..=(y2 - 1) / TILE_SIZE {
+                for tile_x in x1 / TILE_SIZE..=(x2 - 1) / TILE_SIZE {
+                    tiles.entry((tile_x, tile_y)).or_default().push(i);
+                }
+            }
+        }
+
+        let mut multiplicity = vec![0u32; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                let mut n = diff[y * (width + 1) + x];
+                if x > 0 {
+                    n += multiplicity[y * width + x - 1] as i32;
+                }
+                if y > 0 {
+                    n += multiplicity[(y - 1) * width + x] as i32;
+                }
+                if x > 0 && y > 0 {
+                    n -= multiplicity[(y - 1) * width + x - 1] as i32;
+                }
+                multiplicity[y * width + x] = n as u32;
+            }
+        }
+
+        FabricIndex {
+            width,
+            height,
+            multiplicity,
+            claims,
+            tiles,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Region covering all the claims.
+    pub fn bounds(&self) -> Region {
+        Region {
+            left: 0,
+            top: 0,
+            wide: self.width,
+            tall: self.height,
+        }
+    }
+
+    /// IDs and regions of all claims, in input order.
+    pub fn claims(&self) -> &[(usize, Region)] {
+        &self.claims
+    }
+
+    /// Largest number of claims covering a single square inch.
+    pub fn max_multiplicity(&self) -> usize {
+        self.multiplicity.iter().cloned().max().unwrap_or(0) as usize
+    }
+
+    /// Number of claims covering square inch `(x, y)`.
+    pub fn multiplicity(&self, x: usize, y: usize) -> usize {
+        if x < self.width && y < self.height {
+            self.multiplicity[y * self.width + x] as usize
+        } else {
+            0
+        }
+    }
+
+    /// IDs of the claims covering square inch `(x, y)`, in input order.
+    pub fn claims_at(&self, x: usize, y: usize) -> Vec<usize> {
+        match self.tiles.get(&(x / TILE_SIZE, y / TILE_SIZE)) {
+            Some(candidates) => candidates
+                .iter()
+                .map(|&i| &self.claims[i])
+                .filter(|(_id, region)| {
+                    region.left <= x
+                        && x < region.left + region.wide
+                        && region.top <= y
+                        && y < region.top + region.tall
+                })
+                .map(|(id, _region)| *id)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Prefix sums over the square inches claimed by at least `k` claims,
+    /// answering region queries in constant time.
+    pub fn at_least(&self, k: usize) -> Coverage {
+        let stride = self.width + 1;
+        let mut prefix = vec![0u32; stride * (self.height + 1)];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let claimed = (self.multiplicity[y * self.width + x] as usize >= k) as u32;
+                prefix[(y + 1) * stride + x + 1] =
+                    claimed + prefix[y * stride + x + 1] + prefix[(y + 1) * stride + x]
+                        - prefix[y * stride + x];
+            }
+        }
+        Coverage {
+            width: self.width,
+            height: self.height,
+            prefix,
+        }
+    }
+
+    /// IDs of the claims not overlapping any other claim, in input order.
+    pub fn intact_claims(&self) -> Vec<usize> {
+        let overlapped = self.at_least(2);
+        self.claims
+            .iter()
+            .filter(|(_id, region)| overlapped.count(region) == 0)
+            .map(|(id, _region)| *id)
+            .collect()
+    }
+}
+
+/// Square inches claimed by at least `k` claims, see [`FabricIndex::at_least()`].
+#[derive(Debug)]
+pub struct Coverage {
+    width: usize,
+    height: usize,
+    /// `prefix[y * (width + 1) + x]`: count within `[0, x) × [0, y)`
+    prefix: Vec<u32>,
+}
+
+impl Coverage {
+    /// Number of square inches of `region` claimed by at least `k` claims.
+    pub fn count(&self, region: &Region) -> usize {
+        let x1 = region.left.min(self.width);
+        let y1 = region.top.min(self.height);
+        let x2 = region.left.saturating_add(region.wide).min(self.width);
+        let y2 = region.top.saturating_add(region.tall).min(self.height);
+        let stride = self.width + 1;
+        let at = |x: usize, y: usize| self.prefix[y * stride + x] as usize;
+        at(x2, y2) + at(x1, y1) - at(x1, y2) - at(x2, y1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fabric_index::{FabricIndex, Region};
+    use crate::tests::init_logger;
+    use crate::{parse_input, AoC, Day03PreparsedMemoization, PUZZLE_INPUT};
+
+    #[test]
+    fn given() {
+        init_logger();
+
+        let input = "#1 @ 1,3: 4x4
+                     #2 @ 3,1: 4x4
+                     #3 @ 5,5: 2x2";
+        let index = FabricIndex::new(parse_input(input));
+
+        assert_eq!((index.width(), index.height()), (7, 7));
+        assert_eq!(index.claims_at(3, 3), vec![1, 2]);
+        assert_eq!(index.claims_at(5, 5), vec![3]);
+        assert_eq!(index.claims_at(0, 0), Vec::<usize>::new());
+        assert_eq!(index.claims_at(100, 100), Vec::<usize>::new());
+        assert_eq!(index.multiplicity(4, 4), 2);
+
+        assert_eq!(index.at_least(1).count(&index.bounds()), 32);
+        assert_eq!(index.at_least(2).count(&index.bounds()), 4);
+        assert_eq!(index.at_least(3).count(&index.bounds()), 0);
+
+        let region = Region {
+            left: 4,
+            top: 0,
+            wide: 100,
+            tall: 4,
+        };
+        assert_eq!(index.at_least(1).count(&region), 9);
+        assert_eq!(index.at_least(2).count(&region), 1);
+
+        assert_eq!(index.intact_claims(), vec![3]);
+    }
+
+    #[test]
+    fn solution() {
+        init_logger();
+
+        let index = FabricIndex::new(parse_input(PUZZLE_INPUT));
+
+        // Part 1
+        assert_eq!(index.at_least(2).count(&index.bounds()), 100595);
+        // Part 2
+        assert_eq!(index.intact_claims(), vec![415]);
+    }
+
+    #[test]
+    fn match_memoization() {
+        init_logger();
+
+        // Deterministic pseudo-random small claims
+        let mut seed: u64 = 0x2018_1203;
+        let mut next = move |modulo: u64| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((seed >> 33) % modulo) as usize
+        };
+        for _ in 0..50 {
+            let input: Vec<String> = (1..=(1 + next(20)))
+                .map(|id| {
+                    format!(
+                        "#{} @ {},{}: {}x{}",
+                        id,
+                        next(80),
+                        next(80),
+                        1 + next(10),
+                        1 + next(10)
+                    )
+                })
+                .collect();
+            let input = input.join("\n");
+            let index = FabricIndex::new(parse_input(&input));
+
+            let expected = Day03PreparsedMemoization::new(&input).solution_part1();
+            assert_eq!(expected, index.at_least(2).count(&index.bounds()));
+
+            for claim in parse_input(&input) {
+                let ids = index.claims_at(claim.left, claim.top);
+                assert!(ids.contains(&claim.id));
+                assert_eq!(ids.len(), index.multiplicity(claim.left, claim.top));
+            }
+        }
+    }
+}
@@ -89,9 +89,12 @@
 // #[macro_use]
 // extern crate log;
 
+#[cfg(feature = "ndarray")]
 extern crate ndarray;
 
-use std::fmt::Debug;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
 
 pub mod initial;
 pub use initial::Day03Initial;
@@ -102,9 +105,22 @@ pub use preparsed::Day03Preparsed;
 pub mod preparsed_memoization;
 pub use preparsed_memoization::Day03PreparsedMemoization;
 
+#[cfg(feature = "ndarray")]
 pub mod preparsed_ndarray;
+#[cfg(feature = "ndarray")]
 pub use preparsed_ndarray::Day03PreparsedNdarray;
 
+pub mod sweep_line;
+pub use sweep_line::Day03SweepLine;
+
+pub mod overlap_graph;
+
+pub mod fabric_index;
+
+pub mod fabric;
+
+pub mod render;
+
 type Day03SolutionPart1 = usize;
 type Day03SolutionPart2 = usize;
 
@@ -129,33 +145,27 @@ pub trait AoC<'a>: Debug {
     }
 }
 
+/// Split every claim of `input` without copying, panicking on a malformed claim.
 pub fn parse_input_str<'a>(input: &'a str) -> impl Iterator<Item = InputStr<'a>> + 'a {
-    input.lines().map(move |line| {
-        let mut s = line.trim().split(' ');
-        // Id
-        let id = &s.next().unwrap()[1..];
-        // Skip @
-        let _ = s.next().unwrap();
-        // Position
-        let mut position = s.next().unwrap().split(',');
-        let left = position.next().unwrap();
-        let top = position.next().unwrap().trim_end_matches(':');
-        // Size
-        let mut size = s.next().unwrap().split('x');
-        let wide = size.next().unwrap();
-        let tall = size.next().unwrap();
-        InputStr {
-            id,
-            left,
-            top,
-            wide,
-            tall,
-        }
+    input.lines().enumerate().map(|(i, line)| {
+        InputStr::try_from(line)
+            .unwrap_or_else(|error| panic!("{}", ClaimError::new(i, line, error)))
     })
 }
 
+/// Parse every claim of `input`, panicking on a malformed claim.
 pub fn parse_input<'a>(input: &'a str) -> impl Iterator<Item = Input> + 'a {
-    parse_input_str(input).map(Input::from)
+    try_parse_input(input).map(|claim| claim.unwrap_or_else(|error| panic!("{}", error)))
+}
+
+/// Parse every claim of `input`, reporting malformed claims instead of
+/// panicking.
+pub fn try_parse_input<'a>(input: &'a str) -> impl Iterator<Item = Result<Input, ClaimError>> + 'a {
+    input.lines().enumerate().map(|(i, line)| {
+        InputStr::try_from(line)
+            .and_then(|claim| Input::try_from(&claim))
+            .map_err(|error| ClaimError::new(i, line, error))
+    })
 }
 
 pub static PUZZLE_INPUT: &str = include_str!("../input");
@@ -174,8 +184,10 @@ pub mod benchmark {
         vec![
             Box::new(Day03Initial::new(PUZZLE_INPUT)),
             Box::new(Day03Preparsed::new(PUZZLE_INPUT)),
+            #[cfg(feature = "ndarray")]
             Box::new(Day03PreparsedNdarray::new(PUZZLE_INPUT)),
             Box::new(Day03PreparsedMemoization::new(PUZZLE_INPUT)),
+            Box::new(Day03SweepLine::new(PUZZLE_INPUT)),
         ]
     }
 }
@@ -198,24 +210,256 @@ pub struct Input {
     tall: usize,
 }
 
+/// Field of a claim `#id @ left,top: widexheight`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimField {
+    Id,
+    /// The `@` separating the ID from the position
+    At,
+    Left,
+    /// The `,` separating left from top
+    Comma,
+    Top,
+    /// The `:` ending the position
+    Colon,
+    Wide,
+    /// The `x` separating wide from tall
+    By,
+    Tall,
+    /// Anything after the size
+    Trailing,
+}
+
+impl Display for ClaimField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ClaimField::Id => "id",
+            ClaimField::At => "'@' separator",
+            ClaimField::Left => "left",
+            ClaimField::Comma => "',' separator",
+            ClaimField::Top => "top",
+            ClaimField::Colon => "':' separator",
+            ClaimField::Wide => "wide",
+            ClaimField::By => "'x' separator",
+            ClaimField::Tall => "tall",
+            ClaimField::Trailing => "trailing text",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Invalid or missing field of a single claim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: ClaimField,
+    /// Raw text found for the field, empty if the field is missing
+    pub text: String,
+}
+
+impl FieldError {
+    fn new(field: ClaimField, text: &str) -> FieldError {
+        FieldError {
+            field,
+            text: text.to_string(),
+        }
+    }
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.text.is_empty() {
+            write!(f, "missing {}", self.field)
+        } else {
+            write!(f, "invalid {} {:?}", self.field, self.text)
+        }
+    }
+}
+
+impl Error for FieldError {}
+
+/// Malformed claim within a puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimError {
+    /// Line number of the claim, starting at 1
+    pub line: usize,
+    /// Raw text of the claim
+    pub claim: String,
+    pub error: FieldError,
+}
+
+impl ClaimError {
+    fn new(index: usize, line: &str, error: FieldError) -> ClaimError {
+        ClaimError {
+            line: index + 1,
+            claim: line.trim().to_string(),
+            error,
+        }
+    }
+}
+
+impl Display for ClaimError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}: {} in claim {:?}",
+            self.line, self.error, self.claim
+        )
+    }
+}
+
+impl Error for ClaimError {}
+
+impl<'a> TryFrom<&'a str> for InputStr<'a> {
+    type Error = FieldError;
+
+    fn try_from(line: &'a str) -> Result<InputStr<'a>, FieldError> {
+        let mut s = line.trim().split(' ');
+        let mut next = |field| match s.next() {
+            Some(token) if !token.is_empty() => Ok(token),
+            _ => Err(FieldError::new(field, "")),
+        };
+
+        // Id
+        let id = next(ClaimField::Id)?;
+        let id = id
+            .strip_prefix('#')
+            .ok_or_else(|| FieldError::new(ClaimField::Id, id))?;
+        // Skip @
+        let at = next(ClaimField::At)?;
+        if at != "@" {
+            return Err(FieldError::new(ClaimField::At, at));
+        }
+        // Position
+        let position = next(ClaimField::Left)?;
+        let (left, top) = position
+            .split_once(',')
+            .ok_or_else(|| FieldError::new(ClaimField::Comma, ""))?;
+        let top = top
+            .strip_suffix(':')
+            .ok_or_else(|| FieldError::new(ClaimField::Colon, ""))?;
+        // Size
+        let size = next(ClaimField::Wide)?;
+        let (wide, tall) = size
+            .split_once('x')
+            .ok_or_else(|| FieldError::new(ClaimField::By, ""))?;
+
+        if let Ok(trailing) = next(ClaimField::Trailing) {
+            return Err(FieldError::new(ClaimField::Trailing, trailing));
+        }
+
+        Ok(InputStr {
+            id,
+            left,
+            top,
+            wide,
+            tall,
+        })
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b InputStr<'a>> for Input {
+    type Error = FieldError;
+
+    fn try_from(input: &'b InputStr<'a>) -> Result<Input, FieldError> {
+        let parse = |field, text: &str| text.parse().map_err(|_| FieldError::new(field, text));
+        Ok(Input {
+            id: parse(ClaimField::Id, input.id)?,
+            left: parse(ClaimField::Left, input.left)?,
+            top: parse(ClaimField::Top, input.top)?,
+            wide: parse(ClaimField::Wide, input.wide)?,
+            tall: parse(ClaimField::Tall, input.tall)?,
+        })
+    }
+}
+
 impl<'a> From<InputStr<'a>> for Input {
-    fn from(input: InputStr<'a>) -> Self {
-        Input {
-            id: input.id.parse().unwrap(),
-            left: input.left.parse().unwrap(),
-            top: input.top.parse().unwrap(),
-            wide: input.wide.parse().unwrap(),
-            tall: input.tall.parse().unwrap(),
+    fn from(input: InputStr<'a>) -> Input {
+        Input::try_from(&input).expect("Invalid claim field")
+    }
+}
+
+/// Same layout as the puzzle input: `#id @ left,top: widexheight`
+impl Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "#{} @ {},{}: {}x{}",
+            self.id, self.left, self.top, self.wide, self.tall
+        )
+    }
+}
+
+/// Claim rectangle, in `u128` so that `left + wide` can't overflow.
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    id: usize,
+    x1: u128,
+    x2: u128,
+    y1: u128,
+    y2: u128,
+}
+
+impl Rect {
+    fn is_empty(&self) -> bool {
+        self.x1 == self.x2 || self.y1 == self.y2
+    }
+
+    fn overlaps(&self, other: &Rect) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.x1 < other.x2
+            && other.x1 < self.x2
+            && self.y1 < other.y2
+            && other.y1 < self.y2
+    }
+}
+
+impl<'a> From<&'a Input> for Rect {
+    fn from(claim: &'a Input) -> Rect {
+        let x1 = claim.left as u128;
+        let y1 = claim.top as u128;
+        Rect {
+            id: claim.id,
+            x1,
+            x2: x1 + claim.wide as u128,
+            y1,
+            y2: y1 + claim.tall as u128,
         }
     }
 }
 
+/// Indices of all pairs of overlapping rectangles, found by sweeping a
+/// vertical line over the rectangles' left edges.
+fn overlapping_pairs(rects: &[Rect]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..rects.len()).collect();
+    order.sort_unstable_by_key(|&i| rects[i].x1);
+
+    let mut pairs = Vec::new();
+    // Rectangles crossing the sweep line
+    let mut active: Vec<usize> = Vec::new();
+    for i in order {
+        let rect = &rects[i];
+        active.retain(|&j| rects[j].x2 > rect.x1);
+        for &j in &active {
+            if rect.overlaps(&rects[j]) {
+                pairs.push((i.min(j), i.max(j)));
+            }
+        }
+        active.push(i);
+    }
+    pairs
+}
+
 #[cfg(test)]
 mod tests {
     extern crate env_logger;
+    use std::convert::TryFrom;
     use std::env;
 
-    use crate::{parse_input, parse_input_str, Input, InputStr};
+    use crate::{
+        parse_input, parse_input_str, try_parse_input, ClaimError, ClaimField, FieldError, Input,
+        InputStr,
+    };
 
     pub fn init_logger() {
         env::var("RUST_LOG")
@@ -300,4 +544,78 @@ mod tests {
             }]
         );
     }
+
+    #[test]
+    fn parse_errors() {
+        init_logger();
+
+        let error = |input: &str| try_parse_input(input).find_map(Result::err).unwrap();
+        let field_error = |input: &str| error(input).error;
+        let invalid = |field, text: &str| FieldError {
+            field,
+            text: text.to_string(),
+        };
+
+        assert_eq!(
+            error("#1 @ 1,3: 4x4\n#2 @ 1,3 4x4"),
+            ClaimError {
+                line: 2,
+                claim: "#2 @ 1,3 4x4".to_string(),
+                error: invalid(ClaimField::Colon, ""),
+            }
+        );
+        assert_eq!(field_error("1 @ 1,3: 4x4"), invalid(ClaimField::Id, "1"));
+        assert_eq!(field_error("#a @ 1,3: 4x4"), invalid(ClaimField::Id, "a"));
+        assert_eq!(field_error("#1 at 1,3: 4x4"), invalid(ClaimField::At, "at"));
+        assert_eq!(
+            field_error("#1 @ -1,3: 4x4"),
+            invalid(ClaimField::Left, "-1")
+        );
+        assert_eq!(field_error("#1 @ 13: 4x4"), invalid(ClaimField::Comma, ""));
+        assert_eq!(field_error("#1 @ 1,: 4x4"), invalid(ClaimField::Top, ""));
+        assert_eq!(
+            field_error("#1 @ 1,3: 4.5x4"),
+            invalid(ClaimField::Wide, "4.5")
+        );
+        assert_eq!(field_error("#1 @ 1,3: 4x"), invalid(ClaimField::Tall, ""));
+        assert_eq!(field_error("#1 @ 1,3: 44"), invalid(ClaimField::By, ""));
+        assert_eq!(field_error("#1 @ 1,3:"), invalid(ClaimField::Wide, ""));
+        assert_eq!(
+            field_error("#1 @ 1,3: 4x4 extra"),
+            invalid(ClaimField::Trailing, "extra")
+        );
+
+        assert_eq!(
+            error("#1 @ 1,3 4x4").to_string(),
+            "line 1: missing ':' separator in claim \"#1 @ 1,3 4x4\""
+        );
+        assert_eq!(
+            error("#1 @ 1,3:").to_string(),
+            "line 1: missing wide in claim \"#1 @ 1,3:\""
+        );
+        assert_eq!(
+            error("#1 @ 13: 4x4").to_string(),
+            "line 1: missing ',' separator in claim \"#1 @ 13: 4x4\""
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "line 3: invalid tall \"4y\"")]
+    fn parse_panics_with_position() {
+        init_logger();
+
+        let input = "#1 @ 1,3: 4x4
+                     #2 @ 3,1: 4x4
+                     #3 @ 5,5: 4x4y";
+        let _ = parse_input(input).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid claim field")]
+    fn from_input_str_panics() {
+        init_logger();
+
+        let claim = InputStr::try_from("#1 @ 1,3: 4x4y").unwrap();
+        let _ = Input::from(claim);
+    }
 }
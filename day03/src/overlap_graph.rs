@@ -0,0 +1,307 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{overlapping_pairs, ClaimError, ClaimField, FieldError, Input, Rect};
+
+/// Graph of the claims, with an edge between every two overlapping claims.
+///
+/// Claims are identified by their ID, which must be unique. Like
+/// `Day03SweepLine`, nothing depends on the fabric size.
+#[derive(Debug)]
+pub struct OverlapGraph {
+    rects: Vec<Rect>,
+    index: HashMap<usize, usize>,
+    /// Indices of the claims overlapping each claim
+    edges: Vec<BTreeSet<usize>>,
+}
+
+impl OverlapGraph {
+    /// Graph of `claims`, panicking on a repeated claim ID.
+    pub fn new(claims: impl IntoIterator<Item = Input>) -> OverlapGraph {
+        OverlapGraph::try_new(claims).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Graph of `claims`, reporting the first claim repeating an earlier ID
+    /// as an invalid ID, at its position within `claims`.
+    pub fn try_new(claims: impl IntoIterator<Item = Input>) -> Result<OverlapGraph, ClaimError> {
+        let mut rects = Vec::new();
+        let mut index = HashMap::new();
+        for (i, claim) in claims.into_iter().enumerate() {
+            if index.insert(claim.id, i).is_some() {
+                let error = FieldError::new(ClaimField::Id, &claim.id.to_string());
+                return Err(ClaimError::new(i, &claim.to_string(), error));
+            }
+            rects.push(Rect::from(&claim));
+        }
+        let mut edges = vec![BTreeSet::new(); rects.len()];
+        for (i, j) in overlapping_pairs(&rects) {
+            edges[i].insert(j);
+            edges[j].insert(i);
+        }
+        Ok(OverlapGraph {
+            rects,
+            index,
+            edges,
+        })
+    }
+
+    /// IDs of all claims, in input order.
+    pub fn ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.rects.iter().map(|rect| rect.id)
+    }
+
+    /// IDs of the claims overlapping claim `id`, sorted. `None` if there is no
+    /// such claim.
+    pub fn conflicts(&self, id: usize) -> Option<Vec<usize>> {
+        let i = *self.index.get(&id)?;
+        let mut conflicts: Vec<usize> = self.edges[i].iter().map(|&j| self.rects[j].id).collect();
+        conflicts.sort_unstable();
+        Some(conflicts)
+    }
+
+    /// IDs of the claims not overlapping any other, sorted.
+    pub fn intact_claims(&self) -> Vec<usize> {
+        let mut intact: Vec<usize> = self
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_i, edges)| edges.is_empty())
+            .map(|(i, _edges)| self.rects[i].id)
+            .collect();
+        intact.sort_unstable();
+        intact
+    }
+
+    /// Groups of claims connected through overlaps, ignoring intact claims.
+    ///
+    /// Each cluster is sorted, and clusters are sorted by their smallest ID.
+    pub fn clusters(&self) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.rects.len()];
+        let mut clusters = Vec::new();
+        for start in 0..self.rects.len() {
+            if visited[start] || self.edges[start].is_empty() {
+                continue;
+            }
+            visited[start] = true;
+            let mut cluster = Vec::new();
+            let mut to_visit = vec![start];
+            while let Some(i) = to_visit.pop() {
+                cluster.push(self.rects[i].id);
+                for &j in &self.edges[i] {
+                    if !visited[j] {
+                        visited[j] = true;
+                        to_visit.push(j);
+                    }
+                }
+            }
+            cluster.sort_unstable();
+            clusters.push(cluster);
+        }
+        clusters.sort_unstable();
+        clusters
+    }
+
+    /// Area shared by claims `id1` and `id2`, `None` if either doesn't exist.
+    pub fn overlap_area(&self, id1: usize, id2: usize) -> Option<u128> {
+        let a = &self.rects[*self.index.get(&id1)?];
+        let b = &self.rects[*self.index.get(&id2)?];
+        let wide = a.x2.min(b.x2).saturating_sub(a.x1.max(b.x1));
+        let tall = a.y2.min(b.y2).saturating_sub(a.y1.max(b.y1));
+        Some(wide * tall)
+    }
+
+    /// Largest number of claims stacked on a single square inch.
+    ///
+    /// Computed with a sweep line over the claims' vertical edges, keeping
+    /// the multiplicity along `y` in a segment tree.
+    pub fn max_stacked(&self) -> usize {
+        let rects: Vec<&Rect> = self.rects.iter().filter(|rect| !rect.is_empty()).collect();
+        if rects.is_empty() {
+            return 0;
+        }
+
+        let mut ys: Vec<u128> = rects
+            .iter()
+            .flat_map(|rect| vec![rect.y1, rect.y2])
+            .collect();
+        ys.sort_unstable();
+        ys.dedup();
+        let y_index = |y: u128| ys.binary_search(&y).unwrap();
+
+        // Removals sort before additions at the same `x`, claims being half-open
+        let mut events: Vec<(u128, i32, usize, usize)> = rects
+            .iter()
+            .flat_map(|rect| {
+                let (from, to) = (y_index(rect.y1), y_index(rect.y2));
+                vec![(rect.x1, 1, from, to), (rect.x2, -1, from, to)]
+            })
+            .collect();
+        events.sort_unstable();
+
+        let mut tree = MaxTree::new(ys.len() - 1);
+        let mut max_stacked = 0;
+        for (_x, delta, from, to) in events {
+            tree.add(from, to, delta);
+            if delta > 0 {
+                max_stacked = max_stacked.max(tree.max());
+            }
+        }
+        max_stacked as usize
+    }
+}
+
+/// Segment tree supporting "add to a range" and "maximum of all" queries.
+struct MaxTree {
+    segments: usize,
+    /// Value added to a whole node
+    add: Vec<i32>,
+    /// Maximum within a node, `add` included
+    max: Vec<i32>,
+}
+
+impl MaxTree {
+    fn new(segments: usize) -> MaxTree {
+        MaxTree {
+            segments,
+            add: vec![0; 4 * segments],
+            max: vec![0; 4 * segments],
+        }
+    }
+
+    fn max(&self) -> i32 {
+        self.max[1]
+    }
+
+    fn add(&mut self, from: usize, to: usize, delta: i32) {
+        let segments = self.segments;
+        self.add_node(1, 0, segments, from, to, delta);
+    }
+
+    fn add_node(&mut self, node: usize, lo: usize, hi: usize, from: usize, to: usize, delta: i32) {
+        if to <= lo || hi <= from {
+            return;
+        }
+        if from <= lo && hi <= to {
+            self.add[node] += delta;
+        } else {
+            let mid = (lo + hi) / 2;
+            self.add_node(2 * node, lo, mid, from, to, delta);
+            self.add_node(2 * node + 1, mid, hi, from, to, delta);
+        }
+        let children = if hi - lo == 1 {
+            0
+        } else {
+            self.max[2 * node].max(self.max[2 * node + 1])
+        };
+        self.max[node] = self.add[node] + children;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::overlap_graph::OverlapGraph;
+    use crate::tests::init_logger;
+    use crate::{parse_input, ClaimError, ClaimField, FieldError, PUZZLE_INPUT};
+
+    #[test]
+    fn given() {
+        init_logger();
+
+        let input = "#1 @ 1,3: 4x4
+                     #2 @ 3,1: 4x4
+                     #3 @ 5,5: 2x2";
+        let graph = OverlapGraph::new(parse_input(input));
+
+        assert_eq!(graph.conflicts(1), Some(vec![2]));
+        assert_eq!(graph.conflicts(3), Some(vec![]));
+        assert_eq!(graph.conflicts(4), None);
+        assert_eq!(graph.intact_claims(), vec![3]);
+        assert_eq!(graph.clusters(), vec![vec![1, 2]]);
+        assert_eq!(graph.overlap_area(1, 2), Some(4));
+        assert_eq!(graph.overlap_area(2, 1), Some(4));
+        assert_eq!(graph.overlap_area(1, 3), Some(0));
+        assert_eq!(graph.overlap_area(1, 1), Some(16));
+        assert_eq!(graph.max_stacked(), 2);
+    }
+
+    #[test]
+    fn duplicate_ids() {
+        init_logger();
+
+        let input = "#1 @ 1,3: 4x4
+                     #2 @ 3,1: 4x4
+                     #1 @ 5,5: 2x2";
+        let error = OverlapGraph::try_new(parse_input(input)).unwrap_err();
+        assert_eq!(
+            error,
+            ClaimError {
+                line: 3,
+                claim: "#1 @ 5,5: 2x2".to_string(),
+                error: FieldError {
+                    field: ClaimField::Id,
+                    text: "1".to_string(),
+                },
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 3: invalid id \"1\" in claim \"#1 @ 5,5: 2x2\""
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: invalid id \"7\"")]
+    fn duplicate_ids_panic() {
+        init_logger();
+
+        let _ = OverlapGraph::new(parse_input("#7 @ 0,0: 1x1\n#7 @ 5,5: 1x1"));
+    }
+
+    #[test]
+    fn clusters() {
+        init_logger();
+
+        // 1-2-3 chain, 4-5 pair, 6 alone, 7 touching 6 only by an edge
+        let input = "#1 @ 0,0: 2x2
+                     #2 @ 1,1: 2x2
+                     #3 @ 2,2: 2x2
+                     #4 @ 10,10: 3x3
+                     #5 @ 11,11: 3x3
+                     #6 @ 20,20: 1x1
+                     #7 @ 21,20: 1x1";
+        let graph = OverlapGraph::new(parse_input(input));
+
+        assert_eq!(graph.clusters(), vec![vec![1, 2, 3], vec![4, 5]]);
+        assert_eq!(graph.conflicts(2), Some(vec![1, 3]));
+        assert_eq!(graph.intact_claims(), vec![6, 7]);
+        // 1 and 3 only touch by a corner
+        assert_eq!(graph.max_stacked(), 2);
+    }
+
+    #[test]
+    fn max_stacked() {
+        init_logger();
+
+        let input = "#1 @ 0,0: 10x10
+                     #2 @ 2,2: 5x5
+                     #3 @ 3,3: 1x1
+                     #4 @ 4,4: 5x5
+                     #5 @ 3,0: 1x10";
+        let graph = OverlapGraph::new(parse_input(input));
+
+        // (3, 3) is covered by 1, 2, 3 and 5
+        assert_eq!(graph.max_stacked(), 4);
+    }
+
+    #[test]
+    fn solution() {
+        init_logger();
+
+        let graph = OverlapGraph::new(parse_input(PUZZLE_INPUT));
+
+        assert_eq!(graph.intact_claims(), vec![415]);
+        assert_eq!(
+            graph.ids().count(),
+            graph.clusters().iter().map(Vec::len).sum::<usize>() + 1
+        );
+    }
+}
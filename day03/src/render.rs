@@ -0,0 +1,210 @@
+//! Rendering of the fabric claim map.
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+use crate::fabric_index::{FabricIndex, Region};
+
+/// Color of the square inches of the intact claims
+pub const INTACT_COLOR: [u8; 3] = [0, 255, 0];
+
+/// Character of the square inches of the intact claims
+pub const INTACT_GLYPH: char = '*';
+
+/// Render `region` of the fabric as in the puzzle description: `.` for
+/// unclaimed square inches, `X` for overlapped ones and the claim ID
+/// otherwise. Claims with IDs of more than one digit are drawn as `#`, and
+/// intact claims as [`INTACT_GLYPH`].
+pub fn to_ascii(index: &FabricIndex, region: &Region) -> String {
+    let intact: HashSet<usize> = index.intact_claims().into_iter().collect();
+    let mut ascii = String::with_capacity((region.wide + 1) * region.tall);
+    for y in region.top..region.top + region.tall {
+        for x in region.left..region.left + region.wide {
+            let c = match index.multiplicity(x, y) {
+                0 => '.',
+                1 => match index.claims_at(x, y)[0] {
+                    id if intact.contains(&id) => INTACT_GLYPH,
+                    id @ 0..=9 => (b'0' + id as u8) as char,
+                    _ => '#',
+                },
+                _ => 'X',
+            };
+            ascii.push(c);
+        }
+        ascii.push('\n');
+    }
+    ascii
+}
+
+/// Heat map color of a square inch claimed `multiplicity` times, going from
+/// black (unclaimed) through blue (single claim) to red (`max_multiplicity`).
+fn heat_color(multiplicity: usize, max_multiplicity: usize) -> [u8; 3] {
+    if multiplicity == 0 {
+        return [0, 0, 0];
+    }
+    let t = if max_multiplicity > 1 {
+        (multiplicity - 1) as f64 / (max_multiplicity - 1) as f64
+    } else {
+        0.0
+    };
+    [(255.0 * t) as u8, 0, (255.0 * (1.0 - t)) as u8]
+}
+
+/// Write the whole fabric as a binary PPM (`P6`) heat map of the claim
+/// multiplicity, one pixel per square inch. Intact claims are drawn in
+/// [`INTACT_COLOR`].
+pub fn write_ppm<W: Write>(index: &FabricIndex, mut out: W) -> io::Result<()> {
+    let (width, height) = (index.width(), index.height());
+    let max_multiplicity = index.max_multiplicity();
+    let intact: HashSet<usize> = index.intact_claims().into_iter().collect();
+
+    let mut pixels = Vec::with_capacity(3 * width * height);
+    for y in 0..height {
+        for x in 0..width {
+            pixels.extend_from_slice(&heat_color(index.multiplicity(x, y), max_multiplicity));
+        }
+    }
+    for (_id, region) in index
+        .claims()
+        .iter()
+        .filter(|(id, _region)| intact.contains(id))
+    {
+        for y in region.top..region.top + region.tall {
+            for x in region.left..region.left + region.wide {
+                let pixel = 3 * (y * width + x);
+                pixels[pixel..pixel + 3].copy_from_slice(&INTACT_COLOR);
+            }
+        }
+    }
+
+    write!(out, "P6\n{} {}\n255\n", width, height)?;
+    out.write_all(&pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fabric_index::{FabricIndex, Region};
+    use crate::render::{to_ascii, write_ppm, INTACT_COLOR, INTACT_GLYPH};
+    use crate::tests::init_logger;
+    use crate::{parse_input, PUZZLE_INPUT};
+
+    #[test]
+    fn ascii_given() {
+        init_logger();
+
+        let input = "#1 @ 1,3: 4x4
+                     #2 @ 3,1: 4x4
+                     #3 @ 5,5: 2x2";
+        let index = FabricIndex::new(parse_input(input));
+        let region = Region {
+            left: 0,
+            top: 0,
+            wide: 8,
+            tall: 8,
+        };
+        let expected = "........
+...2222.
+...2222.
+.11XX22.
+.11XX22.
+.1111**.
+.1111**.
+........
+";
+        assert_eq!(to_ascii(&index, &region), expected);
+    }
+
+    #[test]
+    fn ascii_multi_digit_id() {
+        init_logger();
+
+        let input = "#123 @ 3,2: 5x4
+                     #45 @ 6,4: 3x3";
+        let index = FabricIndex::new(parse_input(input));
+        let region = Region {
+            left: 0,
+            top: 0,
+            wide: 11,
+            tall: 9,
+        };
+        let expected = "...........
+...........
+...#####...
+...#####...
+...###XX#..
+...###XX#..
+......###..
+...........
+...........
+";
+        assert_eq!(to_ascii(&index, &region), expected);
+    }
+
+    #[test]
+    fn ascii_intact() {
+        init_logger();
+
+        // Only the intact claim is highlighted, whatever the length of its ID
+        let input = "#1 @ 0,0: 2x2
+                     #2 @ 1,1: 2x2
+                     #345 @ 4,0: 2x2";
+        let index = FabricIndex::new(parse_input(input));
+        let region = Region {
+            left: 0,
+            top: 0,
+            wide: 6,
+            tall: 3,
+        };
+        let expected = format!("11..{0}{0}\n1X2.{0}{0}\n.22...\n", INTACT_GLYPH);
+        assert_eq!(to_ascii(&index, &region), expected);
+    }
+
+    #[test]
+    fn ppm_given() {
+        init_logger();
+
+        let input = "#1 @ 1,3: 4x4
+                     #2 @ 3,1: 4x4
+                     #3 @ 5,5: 2x2";
+        let index = FabricIndex::new(parse_input(input));
+        let mut ppm = Vec::new();
+        write_ppm(&index, &mut ppm).unwrap();
+
+        let header = b"P6\n7 7\n255\n";
+        assert_eq!(&ppm[..header.len()], &header[..]);
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 3 * 7 * 7);
+
+        let pixel = |x: usize, y: usize| &pixels[3 * (y * 7 + x)..3 * (y * 7 + x) + 3];
+        assert_eq!(pixel(0, 0), &[0, 0, 0]);
+        assert_eq!(pixel(1, 3), &[0, 0, 255]);
+        assert_eq!(pixel(3, 3), &[255, 0, 0]);
+        assert_eq!(pixel(5, 5), &INTACT_COLOR);
+    }
+
+    #[test]
+    fn ppm_solution() {
+        init_logger();
+
+        let index = FabricIndex::new(parse_input(PUZZLE_INPUT));
+        let mut ppm = Vec::new();
+        write_ppm(&index, &mut ppm).unwrap();
+
+        let header = format!("P6\n{} {}\n255\n", index.width(), index.height());
+        assert!(ppm.starts_with(header.as_bytes()));
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 3 * index.width() * index.height());
+
+        // Only claim 415 is highlighted
+        let (_id, claim) = index
+            .claims()
+            .iter()
+            .find(|(id, _region)| *id == 415)
+            .unwrap();
+        let highlighted = pixels
+            .chunks(3)
+            .filter(|pixel| *pixel == &INTACT_COLOR[..])
+            .count();
+        assert_eq!(highlighted, claim.wide * claim.tall);
+    }
+}
@@ -0,0 +1,341 @@
+use std::convert::TryFrom;
+
+use crate::{
+    overlapping_pairs, parse_input, AoC, Day03SolutionPart1, Day03SolutionPart2, Input, Rect,
+};
+
+/// Solution independent of the fabric size: a vertical line sweeps over the
+/// claims' left and right edges, keeping track of the claimed multiplicity
+/// along `y` in a segment tree over the (compressed) `y` coordinates.
+///
+/// Cost only depends on the number of claims, so claims can have huge
+/// coordinates and sizes. Claims cover `[left, left + wide) × [top, top + tall)`:
+/// claims sharing only an edge do not overlap.
+#[derive(Debug)]
+pub struct Day03SweepLine {
+    input: Vec<Input>,
+}
+
+/// Segment tree over the elementary intervals `ys[i]..ys[i + 1]`, giving the
+/// total length covered by at least one and at least two intervals.
+struct CoverageTree<'a> {
+    ys: &'a [u128],
+    /// Number of intervals covering a node entirely
+    cover: Vec<u32>,
+    /// Length covered at least once within a node
+    once: Vec<u128>,
+    /// Length covered at least twice within a node
+    twice: Vec<u128>,
+}
+
+impl<'a> CoverageTree<'a> {
+    fn new(ys: &'a [u128]) -> CoverageTree<'a> {
+        let size = 4 * ys.len().max(1);
+        CoverageTree {
+            ys,
+            cover: vec![0; size],
+            once: vec![0; size],
+            twice: vec![0; size],
+        }
+    }
+
+    fn covered_twice(&self) -> u128 {
+        self.twice[1]
+    }
+
+    /// Add `delta` to the cover count of the elementary intervals `from..to`.
+    fn update(&mut self, from: usize, to: usize, delta: i32) {
+        let segments = self.ys.len() - 1;
+        self.update_node(1, 0, segments, from, to, delta);
+    }
+
+    fn update_node(
+        &mut self,
+        node: usize,
+        lo: usize,
+        hi: usize,
+        from: usize,
+        to: usize,
+        delta: i32,
+    ) {
+        if to <= lo || hi <= from {
+            return;
+        }
+        if from <= lo && hi <= to {
+            self.cover[node] = (self.cover[node] as i32 + delta) as u32;
+        } else {
+            let mid = (lo + hi) / 2;
+            self.update_node(2 * node, lo, mid, from, to, delta);
+            self.update_node(2 * node + 1, mid, hi, from, to, delta);
+        }
+        self.pull(node, lo, hi);
+    }
+
+    fn pull(&mut self, node: usize, lo: usize, hi: usize) {
+        let full = self.ys[hi] - self.ys[lo];
+        let leaf = hi - lo == 1;
+        let (children_once, children_twice) = if leaf {
+            (0, 0)
+        } else {
+            (
+                self.once[2 * node] + self.once[2 * node + 1],
+                self.twice[2 * node] + self.twice[2 * node + 1],
+            )
+        };
+        match self.cover[node] {
+            0 => {
+                self.once[node] = children_once;
+                self.twice[node] = children_twice;
+            }
+            1 => {
+                self.once[node] = full;
+                self.twice[node] = children_once;
+            }
+            _ => {
+                self.once[node] = full;
+                self.twice[node] = full;
+            }
+        }
+    }
+}
+
+impl Day03SweepLine {
+    fn rects(&self) -> Vec<Rect> {
+        self.input
+            .iter()
+            .map(Rect::from)
+            .filter(|rect| !rect.is_empty())
+            .collect()
+    }
+
+    /// Area claimed by two or more claims.
+    pub fn overlapped_area(&self) -> u128 {
+        let rects = self.rects();
+        if rects.is_empty() {
+            return 0;
+        }
+
+        let mut ys: Vec<u128> = rects
+            .iter()
+            .flat_map(|rect| vec![rect.y1, rect.y2])
+            .collect();
+        ys.sort_unstable();
+        ys.dedup();
+        let y_index = |y: u128| ys.binary_search(&y).unwrap();
+
+        // (x, delta, first elementary interval, last elementary interval + 1)
+        let mut events: Vec<(u128, i32, usize, usize)> = rects
+            .iter()
+            .flat_map(|rect| {
+                let (from, to) = (y_index(rect.y1), y_index(rect.y2));
+                vec![(rect.x1, 1, from, to), (rect.x2, -1, from, to)]
+            })
+            .collect();
+        events.sort_unstable();
+
+        let mut tree = CoverageTree::new(&ys);
+        let mut area = 0;
+        let mut previous_x = events[0].0;
+        for (x, delta, from, to) in events {
+            area += tree.covered_twice() * (x - previous_x);
+            previous_x = x;
+            tree.update(from, to, delta);
+        }
+        area
+    }
+
+    /// IDs of the claims not overlapping any other claim, sorted.
+    pub fn intact_claims(&self) -> Vec<usize> {
+        let rects = self.rects();
+
+        let mut overlapping = vec![false; rects.len()];
+        for (i, j) in overlapping_pairs(&rects) {
+            overlapping[i] = true;
+            overlapping[j] = true;
+        }
+
+        // Empty claims can't overlap anything
+        let mut intact: Vec<usize> = rects
+            .iter()
+            .zip(overlapping)
+            .filter(|(_rect, overlapping)| !overlapping)
+            .map(|(rect, _overlapping)| rect.id)
+            .chain(
+                self.input
+                    .iter()
+                    .filter(|claim| claim.wide == 0 || claim.tall == 0)
+                    .map(|claim| claim.id),
+            )
+            .collect();
+        intact.sort_unstable();
+        intact
+    }
+}
+
+impl<'a> AoC<'a> for Day03SweepLine {
+    type SolutionPart1 = Day03SolutionPart1;
+    type SolutionPart2 = Day03SolutionPart2;
+
+    fn description(&self) -> &'static str {
+        "Sweep line and coordinate compression"
+    }
+
+    fn new(input: &'a str) -> Day03SweepLine {
+        Day03SweepLine {
+            input: parse_input(input).collect(),
+        }
+    }
+
+    fn solution_part1(&self) -> Self::SolutionPart1 {
+        usize::try_from(self.overlapped_area()).expect("Overlapped area does not fit in a usize")
+    }
+
+    fn solution_part2(&self) -> Self::SolutionPart2 {
+        let intact = self.intact_claims();
+
+        assert_eq!(intact.len(), 1);
+
+        intact[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod part1 {
+        mod solution {
+            use super::super::super::Day03SweepLine;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 100595;
+                let to_check = Day03SweepLine::new(PUZZLE_INPUT).solution_part1();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day03SweepLine;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = 4;
+                let input = "#1 @ 1,3: 4x4
+                             #2 @ 3,1: 4x4
+                             #3 @ 5,5: 2x2";
+                let to_check = Day03SweepLine::new(input).solution_part1();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+
+        mod extra {
+            use super::super::super::Day03SweepLine;
+            use crate::{tests::init_logger, AoC, Day03PreparsedMemoization};
+
+            #[test]
+            fn huge_claims() {
+                init_logger();
+
+                let input = "#1 @ 1000000000000,0: 1000000000000x1000000000000
+                             #2 @ 1500000000000,500000000000: 1000000000000x1000000000000
+                             #3 @ 0,0: 5x5
+                             #4 @ 18446744073709551610,18446744073709551610: 18446744073709551615x2
+                             #5 @ 18446744073709551614,18446744073709551611: 1x1";
+                let to_check = Day03SweepLine::new(input);
+
+                assert_eq!(to_check.overlapped_area(), 250_000_000_000_000_000_000_001);
+                assert_eq!(to_check.intact_claims(), vec![3]);
+            }
+
+            #[test]
+            fn empty_and_touching_claims() {
+                init_logger();
+
+                let input = "#1 @ 0,0: 2x2
+                             #2 @ 2,0: 2x2
+                             #3 @ 0,2: 4x1
+                             #4 @ 1,1: 0x5";
+                let to_check = Day03SweepLine::new(input);
+
+                assert_eq!(to_check.solution_part1(), 0);
+                assert_eq!(to_check.intact_claims(), vec![1, 2, 3, 4]);
+            }
+
+            #[test]
+            fn match_memoization() {
+                init_logger();
+
+                // Deterministic pseudo-random small claims
+                let mut seed: u64 = 0x2018_1203;
+                let mut next = move |modulo: u64| {
+                    seed = seed
+                        .wrapping_mul(6_364_136_223_846_793_005)
+                        .wrapping_add(1_442_695_040_888_963_407);
+                    ((seed >> 33) % modulo) as usize
+                };
+                for _ in 0..50 {
+                    let input: Vec<String> = (1..=(1 + next(20)))
+                        .map(|id| {
+                            format!(
+                                "#{} @ {},{}: {}x{}",
+                                id,
+                                next(30),
+                                next(30),
+                                1 + next(10),
+                                1 + next(10)
+                            )
+                        })
+                        .collect();
+                    let input = input.join("\n");
+
+                    let expected = Day03PreparsedMemoization::new(&input).solution_part1();
+                    let to_check = Day03SweepLine::new(&input).solution_part1();
+
+                    assert_eq!(expected, to_check, "{}", input);
+                }
+            }
+        }
+    }
+
+    mod part2 {
+        mod solution {
+            use super::super::super::Day03SweepLine;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 415;
+                let to_check = Day03SweepLine::new(PUZZLE_INPUT).solution_part2();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day03SweepLine;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = 3;
+                let input = "#1 @ 1,3: 4x4
+                             #2 @ 3,1: 4x4
+                             #3 @ 5,5: 2x2";
+                let to_check = Day03SweepLine::new(input).solution_part2();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use crate::sleep::{Nap, Window};
+use crate::{Day, GuardId};
+
+/// Sleep statistics of a single guard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardStats {
+    pub id: GuardId,
+    /// Number of shifts in the log
+    pub shifts: usize,
+    /// Minutes asleep within the window, over all shifts
+    pub minutes_asleep: u64,
+    /// Number of shifts the guard was asleep at each minute of the window
+    pub histogram: Vec<u64>,
+    /// Minute of the day within the window the guard is most often asleep,
+    /// and on how many shifts. `None` if never asleep within the window.
+    pub most_consistent: Option<(u64, u64)>,
+    /// Longest nap, whether within the window or not
+    pub longest_nap: Option<Nap>,
+}
+
+/// Sleep analytics of all guards over a time window.
+#[derive(Debug)]
+pub struct Analytics {
+    window: Window,
+    /// Sorted by guard ID
+    guards: Vec<GuardStats>,
+}
+
+impl Analytics {
+    pub fn new(days: &[Day], window: Window) -> Analytics {
+        let mut guards: BTreeMap<GuardId, GuardStats> = BTreeMap::new();
+        for day in days {
+            let stats = guards.entry(day.id).or_insert_with(|| GuardStats {
+                id: day.id,
+                shifts: 0,
+                minutes_asleep: 0,
+                histogram: vec![0; window.len() as usize],
+                most_consistent: None,
+                longest_nap: None,
+            });
+            stats.shifts += 1;
+            for (count, &is_sleeping) in stats.histogram.iter_mut().zip(&day.sleeping(window)) {
+                if is_sleeping {
+                    *count += 1;
+                    stats.minutes_asleep += 1;
+                }
+            }
+            for nap in &day.naps {
+                let is_longer = match stats.longest_nap {
+                    Some(longest) => nap.len() > longest.len(),
+                    None => true,
+                };
+                if is_longer {
+                    stats.longest_nap = Some(*nap);
+                }
+            }
+        }
+
+        let mut guards: Vec<GuardStats> = guards.into_values().collect();
+        for stats in &mut guards {
+            // Earliest minute on ties
+            stats.most_consistent = window
+                .minutes()
+                .zip(stats.histogram.iter().cloned())
+                .filter(|(_minute, count)| *count > 0)
+                .fold(None, |best, (minute, count)| match best {
+                    Some((_, best_count)) if best_count >= count => best,
+                    _ => Some((minute, count)),
+                });
+        }
+
+        Analytics { window, guards }
+    }
+
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    /// Statistics of every guard, sorted by ID.
+    pub fn guards(&self) -> &[GuardStats] {
+        &self.guards
+    }
+
+    pub fn guard(&self, id: GuardId) -> Option<&GuardStats> {
+        self.guards
+            .binary_search_by_key(&id, |stats| stats.id)
+            .ok()
+            .map(|i| &self.guards[i])
+    }
+
+    /// Guard asleep the most within the window (lowest ID on ties).
+    pub fn sleepiest(&self) -> Option<&GuardStats> {
+        self.guards
+            .iter()
+            .rev()
+            .max_by_key(|stats| stats.minutes_asleep)
+    }
+
+    /// Guard most frequently asleep on the same minute (lowest ID on ties).
+    pub fn most_consistent(&self) -> Option<&GuardStats> {
+        self.guards
+            .iter()
+            .rev()
+            .filter(|stats| stats.most_consistent.is_some())
+            .max_by_key(|stats| stats.most_consistent.map(|(_minute, count)| count))
+    }
+
+    /// Longest nap of all, with its guard.
+    pub fn longest_nap(&self) -> Option<(GuardId, Nap)> {
+        self.guards
+            .iter()
+            .rev()
+            .filter_map(|stats| stats.longest_nap.map(|nap| (stats.id, nap)))
+            .max_by_key(|(_id, nap)| nap.len())
+    }
+
+    /// Write one CSV row per guard: summary columns followed by the histogram,
+    /// one column per minute of the window.
+    pub fn write_csv<W: Write>(&self, mut out: W) -> io::Result<()> {
+        write!(
+            out,
+            "id,shifts,minutes_asleep,most_consistent_minute,most_consistent_shifts,\
+             longest_nap_start,longest_nap_minutes"
+        )?;
+        for minute in self.window.minutes() {
+            write!(out, ",{}", clock(minute))?;
+        }
+        writeln!(out)?;
+
+        for stats in &self.guards {
+            write!(
+                out,
+                "{},{},{},",
+                stats.id, stats.shifts, stats.minutes_asleep
+            )?;
+            match stats.most_consistent {
+                Some((minute, count)) => write!(out, "{},{},", clock(minute), count)?,
+                None => write!(out, ",,")?,
+            }
+            match stats.longest_nap {
+                Some(nap) => write!(out, "{},{}", nap.start, nap.len())?,
+                None => write!(out, ",")?,
+            }
+            for count in &stats.histogram {
+                write!(out, ",{}", count)?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = Vec::new();
+        self.write_csv(&mut csv).unwrap();
+        String::from_utf8(csv).unwrap()
+    }
+
+    /// Histograms of all guards, with the minute headers of the puzzle's
+    /// day-by-minute table but one row per guard instead of one per day.
+    ///
+    /// Each cell is the number of shifts the guard was asleep at that minute,
+    /// `.` for never, `1` to `9` then `a` to `z` for 10 to 35, and `#` above.
+    /// Hours are shown above the minutes when the window spans several hours.
+    pub fn heat_map(&self) -> String {
+        let width = self
+            .guards
+            .iter()
+            .map(|stats| format!("#{}", stats.id).len())
+            .max()
+            .unwrap_or(0)
+            .max(2)
+            + 2;
+        let minutes: Vec<u64> = self.window.minutes().collect();
+
+        let mut map = format!("{:<width$}Minute\n", "ID", width = width);
+        let mut header_row = |digit: &dyn Fn(u64) -> u64| {
+            map.push_str(&" ".repeat(width));
+            for &minute in &minutes {
+                map.push(std::char::from_digit(digit(minute) as u32, 10).unwrap());
+            }
+            map.push('\n');
+        };
+        let hours = |minute: u64| minute / 60;
+        if minutes
+            .iter()
+            .any(|&minute| hours(minute) != hours(minutes[0]))
+        {
+            header_row(&|minute| hours(minute) / 10);
+            header_row(&|minute| hours(minute) % 10);
+        }
+        header_row(&|minute| minute % 60 / 10);
+        header_row(&|minute| minute % 10);
+
+        for stats in &self.guards {
+            map.push_str(&format!(
+                "{:<width$}",
+                format!("#{}", stats.id),
+                width = width
+            ));
+            for &count in &stats.histogram {
+                map.push(match count {
+                    0 => '.',
+                    1..=35 => std::char::from_digit(count as u32, 36).unwrap(),
+                    _ => '#',
+                });
+            }
+            map.push('\n');
+        }
+        map
+    }
+}
+
+/// `HH:MM` of a minute of the day
+fn clock(minute_of_day: u64) -> String {
+    format!("{:02}:{:02}", minute_of_day / 60, minute_of_day % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analytics::Analytics;
+    use crate::sleep::Window;
+    use crate::tests::init_logger;
+    use crate::{parse_input, PUZZLE_INPUT};
+
+    static GIVEN: &str = "[1518-11-01 00:00] Guard #10 begins shift
+                          [1518-11-01 00:05] falls asleep
+                          [1518-11-01 00:25] wakes up
+                          [1518-11-01 00:30] falls asleep
+                          [1518-11-01 00:55] wakes up
+                          [1518-11-01 23:58] Guard #99 begins shift
+                          [1518-11-02 00:40] falls asleep
+                          [1518-11-02 00:50] wakes up
+                          [1518-11-03 00:05] Guard #10 begins shift
+                          [1518-11-03 00:24] falls asleep
+                          [1518-11-03 00:29] wakes up
+                          [1518-11-04 00:02] Guard #99 begins shift
+                          [1518-11-04 00:36] falls asleep
+                          [1518-11-04 00:46] wakes up
+                          [1518-11-05 00:03] Guard #99 begins shift
+                          [1518-11-05 00:45] falls asleep
+                          [1518-11-05 00:55] wakes up";
+
+    #[test]
+    fn given() {
+        init_logger();
+
+        let analytics = Analytics::new(&parse_input(GIVEN), Window::MIDNIGHT_HOUR);
+
+        let ids: Vec<i64> = analytics.guards().iter().map(|stats| stats.id).collect();
+        assert_eq!(ids, vec![10, 99]);
+
+        let guard_10 = analytics.guard(10).unwrap();
+        assert_eq!(guard_10.shifts, 2);
+        assert_eq!(guard_10.minutes_asleep, 50);
+        assert_eq!(guard_10.most_consistent, Some((24, 2)));
+        assert_eq!(guard_10.histogram[24], 2);
+        assert_eq!(guard_10.histogram[29], 0);
+        assert!(analytics.guard(11).is_none());
+
+        assert_eq!(analytics.sleepiest().unwrap().id, 10);
+        let guard_99 = analytics.most_consistent().unwrap();
+        assert_eq!((guard_99.id, guard_99.most_consistent), (99, Some((45, 3))));
+
+        let (id, nap) = analytics.longest_nap().unwrap();
+        assert_eq!(
+            (id, nap.start.to_string(), nap.len()),
+            (10, "1518-11-01 00:30".to_string(), 25)
+        );
+    }
+
+    #[test]
+    fn heat_map() {
+        init_logger();
+
+        let analytics = Analytics::new(&parse_input(GIVEN), Window::MIDNIGHT_HOUR);
+        let expected = "ID   Minute
+     000000000011111111112222222222333333333344444444445555555555
+     012345678901234567890123456789012345678901234567890123456789
+#10  .....111111111111111111121111.1111111111111111111111111.....
+#99  ....................................1111222223222211111.....
+";
+        assert_eq!(analytics.heat_map(), expected);
+
+        let analytics = Analytics::new(&parse_input(GIVEN), Window::new(23 * 60 + 58, 3));
+        let expected = "ID   Minute
+     22000
+     33000
+     55000
+     89012
+#10  .....
+#99  .....
+";
+        assert_eq!(analytics.heat_map(), expected);
+    }
+
+    #[test]
+    fn csv() {
+        init_logger();
+
+        let analytics = Analytics::new(&parse_input(GIVEN), Window::new(0, 6));
+        let expected = "\
+id,shifts,minutes_asleep,most_consistent_minute,most_consistent_shifts,longest_nap_start,longest_nap_minutes,00:00,00:01,00:02,00:03,00:04,00:05
+10,2,1,00:05,1,1518-11-01 00:30,25,0,0,0,0,0,1
+99,3,0,,,1518-11-02 00:40,10,0,0,0,0,0,0
+";
+        assert_eq!(analytics.to_csv(), expected);
+    }
+
+    #[test]
+    fn solution() {
+        init_logger();
+
+        let analytics = Analytics::new(&parse_input(PUZZLE_INPUT), Window::MIDNIGHT_HOUR);
+
+        let sleepiest = analytics.sleepiest().unwrap();
+        assert_eq!(
+            sleepiest.id * sleepiest.most_consistent.unwrap().0 as i64,
+            11367
+        );
+        let most_consistent = analytics.most_consistent().unwrap();
+        assert_eq!(
+            most_consistent.id * most_consistent.most_consistent.unwrap().0 as i64,
+            36896
+        );
+        assert_eq!(
+            analytics.heat_map().lines().count(),
+            3 + analytics.guards().len()
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use crate::sleep::{strategy_1, strategy_2, Window};
+use crate::{parse_input_with, Action, AoC, Day04SolutionPart1, Day04SolutionPart2, Line};
+
+/// Parse the input on every call, like `Day04Initial`, but with a
+/// hand-written line parser instead of regexes.
+#[derive(Debug)]
+pub struct Day04ByteParser<'a> {
+    input: &'a str,
+}
+
+/// Parse a `[YYYY-MM-DD HH:MM] action` line, reading its fixed layout byte
+/// by byte without allocating.
+///
+/// Panics on a malformed line; see `validation::validate()` to report
+/// problems instead.
+pub fn parse_line_bytes(input: &str) -> Line {
+    let bytes = input.trim_start().as_bytes();
+    assert!(
+        bytes.len() > 19
+            && bytes[0] == b'['
+            && bytes[5] == b'-'
+            && bytes[8] == b'-'
+            && bytes[11] == b' '
+            && bytes[14] == b':'
+            && bytes[17] == b']'
+            && bytes[18] == b' ',
+        "Invalid line: {:?}",
+        input
+    );
+
+    let action = match trim_end(&bytes[19..]) {
+        b"wakes up" => Action::WakesUp,
+        b"falls asleep" => Action::FallsAsleep,
+        action => {
+            // "Guard #ID begins shift"
+            let id = action
+                .strip_prefix(b"Guard #")
+                .and_then(|rest| rest.strip_suffix(b" begins shift"))
+                .unwrap_or_else(|| panic!("Invalid action: {:?}", input));
+            Action::BeginsShift(number(id) as i64)
+        }
+    };
+
+    Line {
+        year: number(&bytes[1..5]),
+        month: number(&bytes[6..8]),
+        day: number(&bytes[9..11]),
+        hour: number(&bytes[12..14]),
+        minute: number(&bytes[15..17]),
+        action,
+    }
+}
+
+/// `bytes` without trailing ASCII whitespace
+fn trim_end(bytes: &[u8]) -> &[u8] {
+    let len = bytes
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map_or(0, |i| i + 1);
+    &bytes[..len]
+}
+
+/// Value of a string of ASCII digits, fitting in a guard ID
+fn number(digits: &[u8]) -> u64 {
+    assert!(!digits.is_empty(), "Missing number");
+    digits.iter().fold(0, |value: u64, &digit| {
+        assert!(digit.is_ascii_digit(), "Invalid digit: {:?}", digit as char);
+        value
+            .checked_mul(10)
+            .and_then(|value| value.checked_add(u64::from(digit - b'0')))
+            .filter(|&value| value <= i64::MAX as u64)
+            .unwrap_or_else(|| panic!("Number too large: {:?}", String::from_utf8_lossy(digits)))
+    })
+}
+
+impl<'a> AoC<'a> for Day04ByteParser<'a> {
+    type SolutionPart1 = Day04SolutionPart1;
+    type SolutionPart2 = Day04SolutionPart2;
+
+    fn description(&self) -> &'static str {
+        "Hand-written byte parser"
+    }
+
+    fn new(input: &'a str) -> Day04ByteParser<'a> {
+        Day04ByteParser { input }
+    }
+
+    fn solution_part1(&self) -> Self::SolutionPart1 {
+        let days = parse_input_with(self.input, parse_line_bytes);
+        let (id, minute) = strategy_1(&days, Window::MIDNIGHT_HOUR).unwrap();
+        id * minute as i64
+    }
+
+    fn solution_part2(&self) -> Self::SolutionPart2 {
+        let days = parse_input_with(self.input, parse_line_bytes);
+        let (id, minute) = strategy_2(&days, Window::MIDNIGHT_HOUR).unwrap();
+        id * minute as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod part1 {
+        mod solution {
+            use super::super::super::Day04ByteParser;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 11367;
+                let to_check = Day04ByteParser::new(PUZZLE_INPUT).solution_part1();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day04ByteParser;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = 240;
+                let input = "[1518-11-01 00:00] Guard #10 begins shift
+                             [1518-11-01 00:05] falls asleep
+                             [1518-11-01 00:25] wakes up
+                             [1518-11-01 00:30] falls asleep
+                             [1518-11-01 00:55] wakes up
+                             [1518-11-01 23:58] Guard #99 begins shift
+                             [1518-11-02 00:40] falls asleep
+                             [1518-11-02 00:50] wakes up
+                             [1518-11-03 00:05] Guard #10 begins shift
+                             [1518-11-03 00:24] falls asleep
+                             [1518-11-03 00:29] wakes up
+                             [1518-11-04 00:02] Guard #99 begins shift
+                             [1518-11-04 00:36] falls asleep
+                             [1518-11-04 00:46] wakes up
+                             [1518-11-05 00:03] Guard #99 begins shift
+                             [1518-11-05 00:45] falls asleep
+                             [1518-11-05 00:55] wakes up";
+                let to_check = Day04ByteParser::new(input).solution_part1();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+
+        mod extra {
+            use super::super::super::parse_line_bytes;
+            use crate::{parse_line, tests::init_logger, PUZZLE_INPUT};
+
+            #[test]
+            fn same_as_parse_line() {
+                init_logger();
+
+                for line in PUZZLE_INPUT.lines() {
+                    assert_eq!(parse_line_bytes(line), parse_line(line));
+                }
+            }
+
+            #[test]
+            #[should_panic(expected = "Invalid line")]
+            fn truncated_line() {
+                init_logger();
+
+                parse_line_bytes("[1518-11-01 00:00]");
+            }
+
+            #[test]
+            #[should_panic(expected = "Invalid digit")]
+            fn invalid_number() {
+                init_logger();
+
+                parse_line_bytes("[1518-11-0a 00:00] falls asleep");
+            }
+
+            #[test]
+            #[should_panic(expected = "Invalid action")]
+            fn partial_action() {
+                init_logger();
+
+                parse_line_bytes("[1518-11-01 00:00] fooled around");
+            }
+
+            #[test]
+            #[should_panic(expected = "Invalid action")]
+            fn trailing_action_text() {
+                init_logger();
+
+                parse_line_bytes("[1518-11-01 00:00] Guard #10 begins shift now");
+            }
+
+            #[test]
+            #[should_panic(expected = "Number too large")]
+            fn number_overflow() {
+                init_logger();
+
+                parse_line_bytes("[1518-11-01 00:00] Guard #99999999999999999999 begins shift");
+            }
+        }
+    }
+
+    mod part2 {
+        mod solution {
+            use super::super::super::Day04ByteParser;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 36896;
+                let to_check = Day04ByteParser::new(PUZZLE_INPUT).solution_part2();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day04ByteParser;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = 4455;
+                let input = "[1518-11-01 00:00] Guard #10 begins shift
+                             [1518-11-01 00:05] falls asleep
+                             [1518-11-01 00:25] wakes up
+                             [1518-11-01 00:30] falls asleep
+                             [1518-11-01 00:55] wakes up
+                             [1518-11-01 23:58] Guard #99 begins shift
+                             [1518-11-02 00:40] falls asleep
+                             [1518-11-02 00:50] wakes up
+                             [1518-11-03 00:05] Guard #10 begins shift
+                             [1518-11-03 00:24] falls asleep
+                             [1518-11-03 00:29] wakes up
+                             [1518-11-04 00:02] Guard #99 begins shift
+                             [1518-11-04 00:36] falls asleep
+                             [1518-11-04 00:46] wakes up
+                             [1518-11-05 00:03] Guard #99 begins shift
+                             [1518-11-05 00:45] falls asleep
+                             [1518-11-05 00:55] wakes up";
+                let to_check = Day04ByteParser::new(input).solution_part2();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::fmt::{self, Display};
+
+/// Calendar date, in the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: u64,
+    pub month: u64,
+    pub day: u64,
+}
+
+/// Minutes in a day
+pub const MINUTES_PER_DAY: u64 = 24 * 60;
+
+/// Shifts beginning from this hour on are closer to the next midnight than
+/// to the previous one
+pub const NEXT_MIDNIGHT_HOUR: u64 = 12;
+
+/// Date and time of a log line, ordered chronologically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    pub date: Date,
+    pub hour: u64,
+    pub minute: u64,
+}
+
+// `u64::is_multiple_of()` would need Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+pub fn is_leap_year(year: u64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Number of days in `month` (1 to 12) of `year`.
+pub fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => panic!("Invalid month: {}", month),
+    }
+}
+
+impl Date {
+    /// Days elapsed since -0400-03-01, one 400-year cycle before year 0.
+    ///
+    /// Counting years from March puts the leap day at the end of the year;
+    /// the Gregorian calendar then repeats every 400 years (146097 days).
+    /// Starting a cycle early keeps January and February of year 0, which
+    /// belong to the March-based year -1, from underflowing.
+    pub fn days_since_epoch(&self) -> u64 {
+        let (year, month) = if self.month > 2 {
+            (self.year + 400, self.month - 3)
+        } else {
+            (self.year + 399, self.month + 9)
+        };
+        let (era, year_of_era) = (year / 400, year % 400);
+        let day_of_year = (153 * month + 2) / 5 + self.day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era
+    }
+
+    /// Following day, rolling over to the next month and year.
+    pub fn next(self) -> Date {
+        if self.day < days_in_month(self.year, self.month) {
+            Date {
+                day: self.day + 1,
+                ..self
+            }
+        } else if self.month < 12 {
+            Date {
+                year: self.year,
+                month: self.month + 1,
+                day: 1,
+            }
+        } else {
+            Date {
+                year: self.year + 1,
+                month: 1,
+                day: 1,
+            }
+        }
+    }
+}
+
+impl Timestamp {
+    /// Minutes elapsed since midnight.
+    pub fn minute_of_day(&self) -> u64 {
+        self.hour * 60 + self.minute
+    }
+
+    /// Minutes elapsed since -0400-03-01 00:00, to measure durations across days.
+    pub fn minutes_since_epoch(&self) -> u64 {
+        self.date.days_since_epoch() * MINUTES_PER_DAY + self.minute_of_day()
+    }
+
+    /// Calendar day of the shift beginning at this timestamp.
+    ///
+    /// Guards are on duty during the midnight hour: a shift covers the day of
+    /// the nearest midnight. A shift beginning in the evening covers the
+    /// following day, one beginning after midnight (even after 00:59) covers
+    /// the same day.
+    pub fn shift_date(&self) -> Date {
+        if self.hour < NEXT_MIDNIGHT_HOUR {
+            self.date
+        } else {
+            self.date.next()
+        }
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Same layout as the guard logs: `YYYY-MM-DD HH:MM`
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {:02}:{:02}", self.date, self.hour, self.minute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calendar::{days_in_month, is_leap_year, Date, Timestamp, MINUTES_PER_DAY};
+    use crate::tests::init_logger;
+
+    fn date(year: u64, month: u64, day: u64) -> Date {
+        Date { year, month, day }
+    }
+
+    #[test]
+    fn leap_years() {
+        init_logger();
+
+        assert!(!is_leap_year(1518));
+        assert!(is_leap_year(1520));
+        assert!(!is_leap_year(1700));
+        assert!(is_leap_year(1600));
+        assert_eq!(days_in_month(1518, 2), 28);
+        assert_eq!(days_in_month(1520, 2), 29);
+        assert_eq!(days_in_month(1518, 4), 30);
+        assert_eq!(days_in_month(1518, 12), 31);
+    }
+
+    #[test]
+    fn next() {
+        init_logger();
+
+        assert_eq!(date(1518, 11, 1).next(), date(1518, 11, 2));
+        assert_eq!(date(1518, 4, 30).next(), date(1518, 5, 1));
+        assert_eq!(date(1518, 2, 28).next(), date(1518, 3, 1));
+        assert_eq!(date(1520, 2, 28).next(), date(1520, 2, 29));
+        assert_eq!(date(1520, 2, 29).next(), date(1520, 3, 1));
+        assert_eq!(date(1518, 12, 31).next(), date(1519, 1, 1));
+    }
+
+    #[test]
+    fn days_since_epoch() {
+        init_logger();
+
+        assert_eq!(date(0, 3, 1).days_since_epoch(), 146_097);
+        assert_eq!(date(1, 3, 1).days_since_epoch(), 146_097 + 365);
+        assert_eq!(date(400, 3, 1).days_since_epoch(), 2 * 146_097);
+        // January and February of year 0 (a leap year)
+        assert_eq!(date(0, 1, 1).days_since_epoch(), 146_097 - 60);
+        assert_eq!(date(0, 2, 29).next().days_since_epoch(), 146_097);
+
+        // Consecutive days are one day apart, across months, years and leap days
+        let mut day = date(1518, 1, 1);
+        while day.year < 1521 {
+            assert_eq!(day.next().days_since_epoch(), day.days_since_epoch() + 1);
+            day = day.next();
+        }
+    }
+
+    #[test]
+    fn minutes_since_epoch() {
+        init_logger();
+
+        let before_midnight = Timestamp {
+            date: date(1518, 12, 31),
+            hour: 23,
+            minute: 58,
+        };
+        let after_midnight = Timestamp {
+            date: date(1519, 1, 1),
+            hour: 0,
+            minute: 2,
+        };
+        assert_eq!(before_midnight.minute_of_day(), MINUTES_PER_DAY - 2);
+        assert_eq!(
+            after_midnight.minutes_since_epoch() - before_midnight.minutes_since_epoch(),
+            4
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        init_logger();
+
+        let before_midnight = Timestamp {
+            date: date(1518, 4, 30),
+            hour: 23,
+            minute: 58,
+        };
+        let after_midnight = Timestamp {
+            date: date(1518, 5, 1),
+            hour: 0,
+            minute: 2,
+        };
+        assert!(before_midnight < after_midnight);
+        assert_eq!(before_midnight.to_string(), "1518-04-30 23:58");
+        assert_eq!(before_midnight.shift_date(), after_midnight.shift_date());
+        assert_eq!(after_midnight.shift_date(), date(1518, 5, 1));
+    }
+
+    #[test]
+    fn shift_date() {
+        init_logger();
+
+        let at = |hour, minute| Timestamp {
+            date: date(1518, 5, 1),
+            hour,
+            minute,
+        };
+        assert_eq!(at(0, 59).shift_date(), date(1518, 5, 1));
+        assert_eq!(at(1, 0).shift_date(), date(1518, 5, 1));
+        assert_eq!(at(1, 30).shift_date(), date(1518, 5, 1));
+        assert_eq!(at(11, 59).shift_date(), date(1518, 5, 1));
+        assert_eq!(at(12, 0).shift_date(), date(1518, 5, 2));
+        assert_eq!(at(23, 0).shift_date(), date(1518, 5, 2));
+    }
+}
@@ -106,13 +106,24 @@
 // #[macro_use]
 // extern crate log;
 
+#[cfg(feature = "regex-parser")]
 #[macro_use]
 extern crate lazy_static;
+#[cfg(feature = "regex-parser")]
 extern crate regex;
 
 use std::fmt::Debug;
 
-use regex::Regex;
+pub mod calendar;
+use calendar::{Date, Timestamp};
+
+pub mod sleep;
+use sleep::{Nap, Window};
+
+pub mod analytics;
+
+pub mod validation;
+use validation::LogError;
 
 pub mod initial;
 pub use initial::Day04Initial;
@@ -123,6 +134,12 @@ pub use preparsed::Day04PreParsed;
 pub mod preparsed_full;
 pub use preparsed_full::Day04PreParsedFull;
 
+pub mod strategy;
+pub use strategy::Day04Strategies;
+
+pub mod byte_parser;
+pub use byte_parser::Day04ByteParser;
+
 type Day04SolutionPart1 = i64;
 type Day04SolutionPart2 = i64;
 
@@ -147,16 +164,29 @@ pub trait AoC<'a>: Debug {
     }
 }
 
-type MidnightHour = Vec<bool>;
 type GuardId = i64;
 
+/// Shift of guard `id`, dated by the calendar day whose midnight hour it covers.
 #[derive(Debug, Eq, PartialEq)]
 pub struct Day {
     year: u64,
     month: u64,
     day: u64,
     id: GuardId,
-    sleeping: MidnightHour,
+    naps: Vec<Nap>,
+}
+
+impl Day {
+    /// Whether the guard was asleep at each minute of `window`.
+    pub fn sleeping(&self, window: Window) -> Vec<bool> {
+        let mut sleeping = vec![false; window.len() as usize];
+        for minute in self.naps.iter().flat_map(Nap::minutes_of_day) {
+            if let Some(i) = window.index_of(minute) {
+                sleeping[i] = true;
+            }
+        }
+        sleeping
+    }
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -176,7 +206,24 @@ pub struct Line {
     action: Action,
 }
 
+impl Line {
+    pub fn timestamp(&self) -> Timestamp {
+        Timestamp {
+            date: Date {
+                year: self.year,
+                month: self.month,
+                day: self.day,
+            },
+            hour: self.hour,
+            minute: self.minute,
+        }
+    }
+}
+
+#[cfg(feature = "regex-parser")]
 pub fn parse_line(input: &str) -> Line {
+    use regex::Regex;
+
     lazy_static! {
         static ref RE_LINE: Regex = Regex::new(
             r"(?x)\[
@@ -214,32 +261,27 @@ pub fn parse_line(input: &str) -> Line {
     }
 }
 
+#[cfg(not(feature = "regex-parser"))]
+pub fn parse_line(input: &str) -> Line {
+    byte_parser::parse_line_bytes(input)
+}
+
 pub fn parse_input(input: &str) -> Vec<Day> {
-    // Build a vector of `Line`, sorted by timestamp
-    const MINUTES_PER_HOUR: u64 = 60;
-    const HOURS_PER_DAY: u64 = 24;
-    const DAYS_PER_MONTH: u64 = 31;
-    const MONTHS_PER_YEAR: u64 = 12;
+    parse_input_with(input, parse_line)
+}
 
-    const MINUTES_PER_DAY: u64 = HOURS_PER_DAY * MINUTES_PER_HOUR;
-    const MINUTES_PER_MONTH: u64 = DAYS_PER_MONTH * MINUTES_PER_DAY;
-    const MINUTES_PER_YEAR: u64 = MONTHS_PER_YEAR * MINUTES_PER_MONTH;
+/// Build the guards' days from `input`, parsing each line with `parse_line`.
+fn parse_input_with<F>(input: &str, parse_line: F) -> Vec<Day>
+where
+    F: Fn(&str) -> Line,
+{
+    days_from_lines(input.lines().map(parse_line).collect())
+}
 
+/// Build the guards' days from the parsed lines of a log, in any order.
+fn days_from_lines(mut lines: Vec<Line>) -> Vec<Day> {
     // Sort lines by date, normalizing the guards beginning of shifts
-    let lines: Vec<Line> = {
-        let mut tmp_lines: Vec<Line> = input.lines().map(|line| parse_line(line)).collect();
-
-        // Sort by date
-        tmp_lines.as_mut_slice().sort_by_key(|line| {
-            line.year * MINUTES_PER_YEAR
-                + line.month * MINUTES_PER_MONTH
-                + line.day * MINUTES_PER_DAY
-                + line.hour * MINUTES_PER_HOUR
-                + line.minute
-        });
-
-        tmp_lines
-    };
+    lines.as_mut_slice().sort_by_key(Line::timestamp);
 
     // Loop for every day
     // debug!("lines: {:#?}", lines);
@@ -255,26 +297,39 @@ pub fn parse_input(input: &str) -> Vec<Day> {
                 _ => panic!("First line should be a 'Guard #XX begins shift'"),
             };
 
+            // Shifts beginning before midnight belong to the next day
+            let date = first_line.timestamp().shift_date();
+
             Day {
-                year: first_line.year,
-                month: first_line.month,
-                day: first_line.day,
+                year: date.year,
+                month: date.month,
+                day: date.day,
                 id: first_line_id,
-                sleeping: vec![false; MINUTES_PER_HOUR as usize],
+                naps: Vec::new(),
             }
         };
 
-        let mut per_minute_sleep_actions: Vec<i8> = vec![0; MINUTES_PER_HOUR as usize];
-        loop {
+        let mut asleep_since: Option<Timestamp> = None;
+        let shift_end = loop {
             match lines_iter.peek() {
                 // Stop when no more lines
                 None => {
-                    break;
+                    // The last shift ends with the day
+                    let date = Date {
+                        year: day.year,
+                        month: day.month,
+                        day: day.day,
+                    };
+                    break Timestamp {
+                        date: date.next(),
+                        hour: 0,
+                        minute: 0,
+                    };
                 }
                 Some(line) => {
                     if let Action::BeginsShift(..) = line.action {
                         // We've hit a shift change. Break the loop
-                        break;
+                        break line.timestamp();
                     }
                 }
             }
@@ -282,22 +337,27 @@ pub fn parse_input(input: &str) -> Vec<Day> {
             let line = lines_iter.next().unwrap();
             match line.action {
                 Action::FallsAsleep => {
-                    per_minute_sleep_actions[line.minute as usize] = -1;
+                    asleep_since = asleep_since.or_else(|| Some(line.timestamp()));
                 }
                 Action::WakesUp => {
-                    per_minute_sleep_actions[line.minute as usize] = 1;
+                    if let Some(start) = asleep_since.take() {
+                        day.naps.push(Nap {
+                            start,
+                            end: line.timestamp(),
+                        });
+                    }
                 }
                 Action::BeginsShift(..) => unreachable!(),
             }
-        }
+        };
 
-        day.sleeping = per_minute_sleep_actions
-            .iter()
-            .scan(0, |cum_sum, action| {
-                *cum_sum += action;
-                Some(*cum_sum)
-            }).map(|action| action < 0)
-            .collect();
+        // A guard still asleep sleeps until the end of the shift
+        if let Some(start) = asleep_since {
+            day.naps.push(Nap {
+                start,
+                end: shift_end,
+            });
+        }
 
         days.push(day);
     }
@@ -305,6 +365,12 @@ pub fn parse_input(input: &str) -> Vec<Day> {
     days
 }
 
+/// Parse the guard log after validating it, reporting all its problems
+/// instead of panicking.
+pub fn try_parse_input(input: &str) -> Result<Vec<Day>, Vec<LogError>> {
+    validation::validated_lines(input).map(days_from_lines)
+}
+
 pub static PUZZLE_INPUT: &str = include_str!("../input");
 
 pub mod benchmark {
@@ -322,6 +388,8 @@ pub mod benchmark {
             Box::new(Day04Initial::new(PUZZLE_INPUT)),
             Box::new(Day04PreParsed::new(PUZZLE_INPUT)),
             Box::new(Day04PreParsedFull::new(PUZZLE_INPUT)),
+            Box::new(Day04Strategies::new(PUZZLE_INPUT)),
+            Box::new(Day04ByteParser::new(PUZZLE_INPUT)),
         ]
     }
 }
@@ -333,7 +401,9 @@ mod tests {
 
     use crate::{parse_input, parse_line};
 
-    use crate::{Action, Day, Line};
+    use crate::calendar::{Date, Timestamp};
+    use crate::sleep::{Nap, Window};
+    use crate::{Action, Day, GuardId, Line};
 
     pub fn init_logger() {
         env::var("RUST_LOG")
@@ -419,6 +489,21 @@ mod tests {
                      [1518-11-05 00:03] Guard #99 begins shift
                      [1518-11-05 00:45] falls asleep
                      [1518-11-05 00:55] wakes up";
+        let nap = |day, start: (u64, u64), end: (u64, u64)| {
+            let at = |(hour, minute)| Timestamp {
+                date: Date {
+                    year: 1518,
+                    month: 11,
+                    day,
+                },
+                hour,
+                minute,
+            };
+            Nap {
+                start: at(start),
+                end: at(end),
+            }
+        };
         let parsed: Vec<Day> = parse_input(input);
         assert_eq!(
             parsed,
@@ -428,72 +513,119 @@ mod tests {
                     month: 11,
                     day: 1,
                     id: 10,
-                    sleeping: vec![
-                        false, false, false, false, false, true, true, true, true, true, true,
-                        true, true, true, true, true, true, true, true, true, true, true, true,
-                        true, true, false, false, false, false, false, true, true, true, true,
-                        true, true, true, true, true, true, true, true, true, true, true, true,
-                        true, true, true, true, true, true, true, true, true, false, false, false,
-                        false, false
-                    ]
+                    naps: vec![nap(1, (0, 5), (0, 25)), nap(1, (0, 30), (0, 55))],
                 },
                 Day {
                     year: 1518,
                     month: 11,
-                    day: 1,
+                    day: 2,
                     id: 99,
-                    sleeping: vec![
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, false, false, false, false, false, true,
-                        true, true, true, true, true, true, true, true, true, false, false, false,
-                        false, false, false, false, false, false, false
-                    ]
+                    naps: vec![nap(2, (0, 40), (0, 50))],
                 },
                 Day {
                     year: 1518,
                     month: 11,
                     day: 3,
                     id: 10,
-                    sleeping: vec![
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, true, true, true, true, true, false, false,
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, false, false, false, false
-                    ]
+                    naps: vec![nap(3, (0, 24), (0, 29))],
                 },
                 Day {
                     year: 1518,
                     month: 11,
                     day: 4,
                     id: 99,
-                    sleeping: vec![
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, false, true, true, true, true, true,
-                        true, true, true, true, true, false, false, false, false, false, false,
-                        false, false, false, false, false, false, false, false
-                    ]
+                    naps: vec![nap(4, (0, 36), (0, 46))],
                 },
                 Day {
                     year: 1518,
                     month: 11,
                     day: 5,
                     id: 99,
-                    sleeping: vec![
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, false, false, false, false, false,
-                        false, false, false, false, false, true, true, true, true, true, true,
-                        true, true, true, true, false, false, false, false, false
-                    ]
+                    naps: vec![nap(5, (0, 45), (0, 55))],
                 }
             ]
         );
+        assert_eq!(
+            parsed[0].sleeping(Window::MIDNIGHT_HOUR),
+            vec![
+                false, false, false, false, false, true, true, true, true, true, true, true, true,
+                true, true, true, true, true, true, true, true, true, true, true, true, false,
+                false, false, false, false, true, true, true, true, true, true, true, true, true,
+                true, true, true, true, true, true, true, true, true, true, true, true, true, true,
+                true, true, false, false, false, false, false
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_shift_attribution() {
+        init_logger();
+
+        // Unsorted, with shifts beginning before midnight at the end of a
+        // month, of February and of the year
+        let input = "[1519-01-01 00:10] falls asleep
+                     [1518-04-30 23:58] Guard #10 begins shift
+                     [1518-05-01 00:30] falls asleep
+                     [1518-12-31 23:50] Guard #30 begins shift
+                     [1518-05-01 00:31] wakes up
+                     [1519-01-01 00:11] wakes up
+                     [1518-02-28 23:59] Guard #20 begins shift
+                     [1518-03-01 00:00] falls asleep
+                     [1518-03-01 00:01] wakes up
+                     [1518-03-02 00:00] Guard #10 begins shift";
+        let parsed: Vec<(u64, u64, u64, GuardId)> = parse_input(input)
+            .iter()
+            .map(|day| (day.year, day.month, day.day, day.id))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                (1518, 3, 1, 20),
+                (1518, 3, 2, 10),
+                (1518, 5, 1, 10),
+                (1519, 1, 1, 30),
+            ]
+        );
+
+        let asleep: Vec<Vec<usize>> = parse_input(input)
+            .iter()
+            .map(|day| {
+                let sleeping = day.sleeping(Window::MIDNIGHT_HOUR);
+                (0..60).filter(|&minute| sleeping[minute]).collect()
+            })
+            .collect();
+        assert_eq!(asleep, vec![vec![0], vec![], vec![30], vec![10]]);
+    }
+
+    #[test]
+    fn parse_leap_day() {
+        init_logger();
+
+        let input = "[1520-02-28 23:59] Guard #1 begins shift
+                     [1520-02-29 23:59] Guard #2 begins shift";
+        let parsed: Vec<(u64, u64, u64, GuardId)> = parse_input(input)
+            .iter()
+            .map(|day| (day.year, day.month, day.day, day.id))
+            .collect();
+        assert_eq!(parsed, vec![(1520, 2, 29, 1), (1520, 3, 1, 2)]);
+    }
+
+    #[test]
+    fn parse_shift_after_midnight_hour() {
+        init_logger();
+
+        let input = "[1518-11-01 23:58] Guard #10 begins shift
+                     [1518-11-02 01:30] Guard #20 begins shift
+                     [1518-11-02 01:40] falls asleep
+                     [1518-11-02 01:50] wakes up
+                     [1518-11-03 00:00] Guard #10 begins shift";
+        let parsed: Vec<(u64, u64, u64, GuardId)> = parse_input(input)
+            .iter()
+            .map(|day| (day.year, day.month, day.day, day.id))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![(1518, 11, 2, 10), (1518, 11, 2, 20), (1518, 11, 3, 10)]
+        );
     }
 }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::sleep::Window;
 use crate::GuardId;
 use crate::{parse_input, AoC, Day04SolutionPart1, Day04SolutionPart2};
 
@@ -22,7 +23,7 @@ impl<'a> AoC<'a> for Day04PreParsedFull {
                 .iter()
                 .map(|day| {
                     let hours_slept_int: Vec<i64> = day
-                        .sleeping
+                        .sleeping(Window::MIDNIGHT_HOUR)
                         .iter()
                         .map(|&is_sleeping| if is_sleeping { 1 } else { 0 })
                         .collect();
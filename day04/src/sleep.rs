@@ -0,0 +1,236 @@
+use crate::analytics::Analytics;
+use crate::calendar::{Timestamp, MINUTES_PER_DAY};
+use crate::strategy::{MostAsleep, MostConsistent, Strategy};
+use crate::{Day, GuardId};
+
+/// Time a guard spent asleep, from `start` (included) to `end` (excluded).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nap {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+impl Nap {
+    /// Number of minutes asleep.
+    pub fn len(&self) -> u64 {
+        self.end
+            .minutes_since_epoch()
+            .saturating_sub(self.start.minutes_since_epoch())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Minute of the day (minutes after midnight) of every minute asleep.
+    pub fn minutes_of_day(&self) -> impl Iterator<Item = u64> {
+        let start = self.start.minute_of_day();
+        (0..self.len()).map(move |minute| (start + minute) % MINUTES_PER_DAY)
+    }
+}
+
+/// Time of day window, from minute `start` after midnight (included) to
+/// minute `end` (excluded). A window with `end < start` wraps around midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    start: u64,
+    end: u64,
+}
+
+impl Window {
+    /// 00:00 to 00:59, the hour of the puzzle's strategies
+    pub const MIDNIGHT_HOUR: Window = Window { start: 0, end: 60 };
+    pub const FULL_DAY: Window = Window {
+        start: 0,
+        end: MINUTES_PER_DAY,
+    };
+
+    pub fn new(start: u64, end: u64) -> Window {
+        assert!(start < MINUTES_PER_DAY, "Invalid window start: {}", start);
+        assert!(end <= MINUTES_PER_DAY, "Invalid window end: {}", end);
+        Window { start, end }
+    }
+
+    /// Number of minutes in the window.
+    pub fn len(&self) -> u64 {
+        if self.start <= self.end {
+            self.end - self.start
+        } else {
+            self.end + MINUTES_PER_DAY - self.start
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Minute of the day of each minute of the window, in order.
+    pub fn minutes(&self) -> impl Iterator<Item = u64> {
+        let start = self.start;
+        (0..self.len()).map(move |i| (start + i) % MINUTES_PER_DAY)
+    }
+
+    /// Position within the window of `minute_of_day`, if it's part of it.
+    pub fn index_of(&self, minute_of_day: u64) -> Option<usize> {
+        let i = (minute_of_day + MINUTES_PER_DAY - self.start) % MINUTES_PER_DAY;
+        if i < self.len() {
+            Some(i as usize)
+        } else {
+            None
+        }
+    }
+}
+
+/// Strategy 1: the guard asleep the most within `window` (lowest ID on
+/// ties), and the minute of the day (within `window`) that guard is most
+/// often asleep. `None` if that guard never sleeps within `window`.
+///
+/// Same as `strategy::MostAsleep`.
+pub fn strategy_1(days: &[Day], window: Window) -> Option<(GuardId, u64)> {
+    MostAsleep.select(&Analytics::new(days, window))
+}
+
+/// Strategy 2: of all guards, the one most frequently asleep on the same
+/// minute of the day within `window` (lowest ID on ties), and that minute.
+///
+/// Same as `strategy::MostConsistent`.
+pub fn strategy_2(days: &[Day], window: Window) -> Option<(GuardId, u64)> {
+    MostConsistent.select(&Analytics::new(days, window))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sleep::{strategy_1, strategy_2, Window};
+    use crate::tests::init_logger;
+    use crate::{parse_input, try_parse_input, PUZZLE_INPUT};
+
+    static GIVEN: &str = "[1518-11-01 00:00] Guard #10 begins shift
+                          [1518-11-01 00:05] falls asleep
+                          [1518-11-01 00:25] wakes up
+                          [1518-11-01 00:30] falls asleep
+                          [1518-11-01 00:55] wakes up
+                          [1518-11-01 23:58] Guard #99 begins shift
+                          [1518-11-02 00:40] falls asleep
+                          [1518-11-02 00:50] wakes up
+                          [1518-11-03 00:05] Guard #10 begins shift
+                          [1518-11-03 00:24] falls asleep
+                          [1518-11-03 00:29] wakes up
+                          [1518-11-04 00:02] Guard #99 begins shift
+                          [1518-11-04 00:36] falls asleep
+                          [1518-11-04 00:46] wakes up
+                          [1518-11-05 00:03] Guard #99 begins shift
+                          [1518-11-05 00:45] falls asleep
+                          [1518-11-05 00:55] wakes up";
+
+    #[test]
+    fn window() {
+        init_logger();
+
+        let late_evening = Window::new(23 * 60, 60);
+        assert_eq!(late_evening.len(), 120);
+        assert_eq!(late_evening.index_of(23 * 60), Some(0));
+        assert_eq!(late_evening.index_of(0), Some(60));
+        assert_eq!(late_evening.index_of(60), None);
+        assert_eq!(late_evening.minutes().last(), Some(59));
+
+        assert_eq!(Window::FULL_DAY.len(), 24 * 60);
+        assert_eq!(Window::MIDNIGHT_HOUR.index_of(59), Some(59));
+        assert_eq!(Window::MIDNIGHT_HOUR.index_of(23 * 60 + 59), None);
+        assert!(Window::new(10, 10).is_empty());
+    }
+
+    #[test]
+    fn given() {
+        init_logger();
+
+        let days = parse_input(GIVEN);
+        assert_eq!(strategy_1(&days, Window::MIDNIGHT_HOUR), Some((10, 24)));
+        assert_eq!(strategy_2(&days, Window::MIDNIGHT_HOUR), Some((99, 45)));
+        // No one sleeps before midnight
+        assert_eq!(strategy_1(&days, Window::FULL_DAY), Some((10, 24)));
+        // No one sleeps in the window
+        assert_eq!(strategy_1(&days, Window::new(2 * 60, 3 * 60)), None);
+        assert_eq!(strategy_2(&days, Window::new(2 * 60, 3 * 60)), None);
+        assert_eq!(strategy_1(&days, Window::new(10, 10)), None);
+    }
+
+    #[test]
+    fn beyond_midnight_hour() {
+        init_logger();
+
+        let input = "[1518-03-01 22:00] Guard #1 begins shift
+                     [1518-03-01 22:30] falls asleep
+                     [1518-03-02 00:10] wakes up
+                     [1518-03-02 00:20] falls asleep
+                     [1518-03-02 00:30] wakes up
+                     [1518-03-03 00:00] Guard #2 begins shift
+                     [1518-03-03 00:00] falls asleep
+                     [1518-03-03 00:50] wakes up
+                     [1518-03-03 01:15] falls asleep
+                     [1518-03-03 01:20] wakes up";
+        let days = parse_input(input);
+
+        // Only the midnight hour counts for the puzzle
+        assert_eq!(
+            days[0]
+                .sleeping(Window::MIDNIGHT_HOUR)
+                .iter()
+                .filter(|&&s| s)
+                .count(),
+            20
+        );
+        assert_eq!(strategy_1(&days, Window::MIDNIGHT_HOUR), Some((2, 0)));
+
+        // Guard #1 slept 110 minutes in total, guard #2 only 55, each minute
+        // once: earliest minute of the window on ties
+        assert_eq!(strategy_1(&days, Window::FULL_DAY), Some((1, 0)));
+        assert_eq!(
+            strategy_1(&days, Window::new(22 * 60, 60)),
+            Some((1, 22 * 60 + 30))
+        );
+        assert_eq!(strategy_2(&days, Window::new(60, 2 * 60)), Some((2, 75)));
+    }
+
+    #[test]
+    fn year_zero() {
+        init_logger();
+
+        // January of year 0 comes before the March the calendar counts from
+        let input = "[0000-01-01 00:00] Guard #1 begins shift
+                     [0000-01-01 00:10] falls asleep
+                     [0000-01-01 00:20] wakes up";
+        let days = parse_input(input);
+        assert_eq!(days[0].naps[0].len(), 10);
+        assert_eq!(strategy_1(&days, Window::MIDNIGHT_HOUR), Some((1, 10)));
+        assert!(try_parse_input(input).is_ok());
+    }
+
+    #[test]
+    fn ties() {
+        init_logger();
+
+        // Both guards sleep as much, on two minutes each
+        let input = "[1518-03-01 00:00] Guard #20 begins shift
+                     [1518-03-01 00:40] falls asleep
+                     [1518-03-01 00:42] wakes up
+                     [1518-03-02 00:00] Guard #10 begins shift
+                     [1518-03-02 00:30] falls asleep
+                     [1518-03-02 00:32] wakes up";
+        let days = parse_input(input);
+        for _ in 0..10 {
+            assert_eq!(strategy_1(&days, Window::MIDNIGHT_HOUR), Some((10, 30)));
+            assert_eq!(strategy_2(&days, Window::MIDNIGHT_HOUR), Some((10, 30)));
+        }
+    }
+
+    #[test]
+    fn solution() {
+        init_logger();
+
+        let days = parse_input(PUZZLE_INPUT);
+        let (id, minute) = strategy_1(&days, Window::MIDNIGHT_HOUR).unwrap();
+        assert_eq!(id * minute as i64, 11367);
+        let (id, minute) = strategy_2(&days, Window::MIDNIGHT_HOUR).unwrap();
+        assert_eq!(id * minute as i64, 36896);
+    }
+}
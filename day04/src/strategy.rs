@@ -0,0 +1,364 @@
+use std::cmp::Reverse;
+use std::fmt::Debug;
+
+use crate::analytics::{Analytics, GuardStats};
+use crate::sleep::Window;
+use crate::{parse_input, AoC, Day04SolutionPart1, Day04SolutionPart2, GuardId};
+
+/// Way of choosing a guard, and a minute of the day to sneak in, from the
+/// guards' sleep analytics.
+pub trait Strategy: Debug {
+    /// Name to select the strategy with, see [`by_name()`]
+    fn name(&self) -> &'static str;
+
+    fn description(&self) -> &'static str {
+        "None"
+    }
+
+    /// Chosen guard and minute of the day, `None` if no guard fits.
+    fn select(&self, analytics: &Analytics) -> Option<(GuardId, u64)>;
+
+    /// Puzzle answer: the chosen guard's ID multiplied by the chosen minute.
+    fn answer(&self, analytics: &Analytics) -> Option<i64> {
+        self.select(analytics)
+            .map(|(id, minute)| id * minute as i64)
+    }
+}
+
+/// Puzzle part 1: guard asleep the most, at the minute they're most often asleep
+#[derive(Debug)]
+pub struct MostAsleep;
+
+impl Strategy for MostAsleep {
+    fn name(&self) -> &'static str {
+        "most-asleep"
+    }
+
+    fn description(&self) -> &'static str {
+        "Guard with the most minutes asleep, at their most consistent minute"
+    }
+
+    fn select(&self, analytics: &Analytics) -> Option<(GuardId, u64)> {
+        let guard = analytics.sleepiest()?;
+        most_consistent_minute(guard)
+    }
+}
+
+/// Puzzle part 2: guard most frequently asleep on the same minute
+#[derive(Debug)]
+pub struct MostConsistent;
+
+impl Strategy for MostConsistent {
+    fn name(&self) -> &'static str {
+        "most-consistent"
+    }
+
+    fn description(&self) -> &'static str {
+        "Guard most frequently asleep on the same minute, at that minute"
+    }
+
+    fn select(&self, analytics: &Analytics) -> Option<(GuardId, u64)> {
+        let guard = analytics.most_consistent()?;
+        most_consistent_minute(guard)
+    }
+}
+
+/// Guard asleep for the longest single stretch
+#[derive(Debug)]
+pub struct LongestNap;
+
+impl Strategy for LongestNap {
+    fn name(&self) -> &'static str {
+        "longest-nap"
+    }
+
+    fn description(&self) -> &'static str {
+        "Guard with the longest nap, at their most consistent minute"
+    }
+
+    fn select(&self, analytics: &Analytics) -> Option<(GuardId, u64)> {
+        let (id, _nap) = analytics.longest_nap()?;
+        most_consistent_minute(analytics.guard(id)?)
+    }
+}
+
+/// Minute with the lowest chance that the guard on duty is awake, and the
+/// guard most often asleep at that minute
+#[derive(Debug)]
+pub struct LeastAwake;
+
+impl Strategy for LeastAwake {
+    fn name(&self) -> &'static str {
+        "least-awake"
+    }
+
+    fn description(&self) -> &'static str {
+        "Minute with the most shifts asleep, with the guard most often asleep then"
+    }
+
+    fn select(&self, analytics: &Analytics) -> Option<(GuardId, u64)> {
+        // One guard is on duty per shift, so the chance of being awake is
+        // lowest when the most shifts are asleep.
+        let (i, minute, _asleep) = analytics
+            .window()
+            .minutes()
+            .enumerate()
+            .map(|(i, minute)| {
+                let asleep: u64 = analytics
+                    .guards()
+                    .iter()
+                    .map(|guard| guard.histogram[i])
+                    .sum();
+                (i, minute, asleep)
+            })
+            .filter(|(_i, _minute, asleep)| *asleep > 0)
+            // Earliest minute on ties
+            .max_by_key(|(i, _minute, asleep)| (*asleep, Reverse(*i)))?;
+        let guard = analytics
+            .guards()
+            .iter()
+            .rev()
+            .max_by_key(|guard| guard.histogram[i])?;
+        Some((guard.id, minute))
+    }
+}
+
+fn most_consistent_minute(guard: &GuardStats) -> Option<(GuardId, u64)> {
+    guard
+        .most_consistent
+        .map(|(minute, _count)| (guard.id, minute))
+}
+
+/// All built-in strategies.
+pub fn builtin() -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(MostAsleep),
+        Box::new(MostConsistent),
+        Box::new(LongestNap),
+        Box::new(LeastAwake),
+    ]
+}
+
+/// Built-in strategy named `name`.
+pub fn by_name(name: &str) -> Option<Box<dyn Strategy>> {
+    builtin()
+        .into_iter()
+        .find(|strategy| strategy.name() == name)
+}
+
+/// Solution running strategies picked by name over pre-computed analytics.
+#[derive(Debug)]
+pub struct Day04Strategies {
+    analytics: Analytics,
+    part1: Box<dyn Strategy>,
+    part2: Box<dyn Strategy>,
+}
+
+impl Day04Strategies {
+    /// Solution using strategies `part1` and `part2` over `window`, `None` if
+    /// either strategy is unknown.
+    pub fn with_strategies(
+        input: &str,
+        window: Window,
+        part1: &str,
+        part2: &str,
+    ) -> Option<Day04Strategies> {
+        Some(Day04Strategies {
+            analytics: Analytics::new(&parse_input(input), window),
+            part1: by_name(part1)?,
+            part2: by_name(part2)?,
+        })
+    }
+}
+
+impl<'a> AoC<'a> for Day04Strategies {
+    type SolutionPart1 = Day04SolutionPart1;
+    type SolutionPart2 = Day04SolutionPart2;
+
+    fn description(&self) -> &'static str {
+        "Strategies by name"
+    }
+
+    fn new(input: &'a str) -> Day04Strategies {
+        Day04Strategies::with_strategies(
+            input,
+            Window::MIDNIGHT_HOUR,
+            MostAsleep.name(),
+            MostConsistent.name(),
+        )
+        .unwrap()
+    }
+
+    fn solution_part1(&self) -> Self::SolutionPart1 {
+        self.part1.answer(&self.analytics).unwrap()
+    }
+
+    fn solution_part2(&self) -> Self::SolutionPart2 {
+        self.part2.answer(&self.analytics).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod part1 {
+        mod solution {
+            use super::super::super::Day04Strategies;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 11367;
+                let to_check = Day04Strategies::new(PUZZLE_INPUT).solution_part1();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day04Strategies;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = 240;
+                let input = "[1518-11-01 00:00] Guard #10 begins shift
+                             [1518-11-01 00:05] falls asleep
+                             [1518-11-01 00:25] wakes up
+                             [1518-11-01 00:30] falls asleep
+                             [1518-11-01 00:55] wakes up
+                             [1518-11-01 23:58] Guard #99 begins shift
+                             [1518-11-02 00:40] falls asleep
+                             [1518-11-02 00:50] wakes up
+                             [1518-11-03 00:05] Guard #10 begins shift
+                             [1518-11-03 00:24] falls asleep
+                             [1518-11-03 00:29] wakes up
+                             [1518-11-04 00:02] Guard #99 begins shift
+                             [1518-11-04 00:36] falls asleep
+                             [1518-11-04 00:46] wakes up
+                             [1518-11-05 00:03] Guard #99 begins shift
+                             [1518-11-05 00:45] falls asleep
+                             [1518-11-05 00:55] wakes up";
+                let to_check = Day04Strategies::new(input).solution_part1();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+    }
+
+    mod part2 {
+        mod solution {
+            use super::super::super::Day04Strategies;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 36896;
+                let to_check = Day04Strategies::new(PUZZLE_INPUT).solution_part2();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day04Strategies;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = 4455;
+                let input = "[1518-11-01 00:00] Guard #10 begins shift
+                             [1518-11-01 00:05] falls asleep
+                             [1518-11-01 00:25] wakes up
+                             [1518-11-01 00:30] falls asleep
+                             [1518-11-01 00:55] wakes up
+                             [1518-11-01 23:58] Guard #99 begins shift
+                             [1518-11-02 00:40] falls asleep
+                             [1518-11-02 00:50] wakes up
+                             [1518-11-03 00:05] Guard #10 begins shift
+                             [1518-11-03 00:24] falls asleep
+                             [1518-11-03 00:29] wakes up
+                             [1518-11-04 00:02] Guard #99 begins shift
+                             [1518-11-04 00:36] falls asleep
+                             [1518-11-04 00:46] wakes up
+                             [1518-11-05 00:03] Guard #99 begins shift
+                             [1518-11-05 00:45] falls asleep
+                             [1518-11-05 00:55] wakes up";
+                let to_check = Day04Strategies::new(input).solution_part2();
+
+                assert_eq!(expected, to_check);
+            }
+        }
+    }
+
+    mod extra {
+        use crate::analytics::Analytics;
+        use crate::sleep::Window;
+        use crate::strategy::{builtin, by_name, Day04Strategies};
+        use crate::{parse_input, tests::init_logger, AoC};
+
+        #[test]
+        fn by_name_and_extra_strategies() {
+            init_logger();
+
+            let input = "[1518-11-01 00:00] Guard #10 begins shift
+                         [1518-11-01 00:05] falls asleep
+                         [1518-11-01 00:45] wakes up
+                         [1518-11-02 00:00] Guard #20 begins shift
+                         [1518-11-02 00:10] falls asleep
+                         [1518-11-02 00:20] wakes up
+                         [1518-11-02 00:30] falls asleep
+                         [1518-11-02 00:50] wakes up
+                         [1518-11-03 00:00] Guard #20 begins shift
+                         [1518-11-03 00:10] falls asleep
+                         [1518-11-03 00:20] wakes up
+                         [1518-11-03 00:30] falls asleep
+                         [1518-11-03 00:50] wakes up";
+            let analytics = Analytics::new(&parse_input(input), Window::MIDNIGHT_HOUR);
+
+            let names: Vec<&str> = builtin().iter().map(|strategy| strategy.name()).collect();
+            assert_eq!(
+                names,
+                vec![
+                    "most-asleep",
+                    "most-consistent",
+                    "longest-nap",
+                    "least-awake"
+                ]
+            );
+            assert!(by_name("coin-flip").is_none());
+
+            let select = |name| by_name(name).unwrap().select(&analytics);
+            // Guard #20 slept 60 minutes, #10 only 40 but in a single nap
+            assert_eq!(select("most-asleep"), Some((20, 10)));
+            assert_eq!(select("most-consistent"), Some((20, 10)));
+            assert_eq!(select("longest-nap"), Some((10, 5)));
+            // All three shifts are asleep from 00:10 to 00:19, and 00:30 to 00:44
+            assert_eq!(select("least-awake"), Some((20, 10)));
+
+            let solution = Day04Strategies::with_strategies(
+                input,
+                Window::MIDNIGHT_HOUR,
+                "longest-nap",
+                "least-awake",
+            )
+            .unwrap();
+            assert_eq!(solution.solution_part1(), 50);
+            assert_eq!(solution.solution_part2(), 200);
+            assert!(Day04Strategies::with_strategies(
+                input,
+                Window::FULL_DAY,
+                "most-asleep",
+                "nope"
+            )
+            .is_none());
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use crate::calendar::{days_in_month, Date, Timestamp};
+use crate::{Action, GuardId, Line};
+
+/// Problem found on a line of a guard log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// The line does not look like `[YYYY-MM-DD HH:MM] action`
+    Malformed,
+    /// The timestamp is not a valid date and time
+    InvalidTimestamp,
+    UnknownAction(String),
+    /// Sleep record while no guard is on duty
+    NoGuardOnDuty,
+    WakesUpWhileAwake,
+    /// The guard fell asleep already, on line `since`
+    FallsAsleepWhileAsleep {
+        since: usize,
+    },
+    /// Guard `id` fell asleep on this line and never woke up before the end
+    /// of the shift
+    AsleepAtShiftEnd {
+        id: GuardId,
+    },
+    /// Same timestamp as line `first`
+    DuplicateTimestamp {
+        first: usize,
+    },
+}
+
+/// Problem on line `line` (starting at 1) of a guard log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogError {
+    pub line: usize,
+    pub problem: Problem,
+}
+
+impl Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.problem {
+            Problem::Malformed => write!(f, "expected '[YYYY-MM-DD HH:MM] action'"),
+            Problem::InvalidTimestamp => write!(f, "invalid date or time"),
+            Problem::UnknownAction(action) => write!(f, "unknown action {:?}", action),
+            Problem::NoGuardOnDuty => write!(f, "no guard on duty"),
+            Problem::WakesUpWhileAwake => write!(f, "guard wakes up without falling asleep"),
+            Problem::FallsAsleepWhileAsleep { since } => {
+                write!(f, "guard falls asleep while asleep since line {}", since)
+            }
+            Problem::AsleepAtShiftEnd { id } => {
+                write!(f, "guard #{} is still asleep at the end of the shift", id)
+            }
+            Problem::DuplicateTimestamp { first } => {
+                write!(f, "same timestamp as line {}", first)
+            }
+        }
+    }
+}
+
+impl Error for LogError {}
+
+/// Check a whole guard log, returning all problems found sorted by line.
+///
+/// Sleep records are checked in chronological order, like `parse_input()`
+/// processes them. An empty vector means `parse_input()` can't fail.
+pub fn validate(input: &str) -> Vec<LogError> {
+    match validated_lines(input) {
+        Ok(_lines) => Vec::new(),
+        Err(errors) => errors,
+    }
+}
+
+/// Lines of a guard log sorted chronologically, or all problems found in it
+/// sorted by line.
+///
+/// Lines are parsed only once, so that `try_parse_input()` builds the guards'
+/// days from exactly what was checked.
+pub fn validated_lines(input: &str) -> Result<Vec<Line>, Vec<LogError>> {
+    let mut errors = Vec::new();
+    let mut records: Vec<(Timestamp, usize, Action)> = Vec::new();
+    for (i, text) in input.lines().enumerate() {
+        match parse_record(text) {
+            Ok((timestamp, action)) => records.push((timestamp, i + 1, action)),
+            Err(problem) => errors.push(LogError {
+                line: i + 1,
+                problem,
+            }),
+        }
+    }
+    records.sort_by_key(|(timestamp, line, _action)| (*timestamp, *line));
+
+    for pair in records.windows(2) {
+        let ((timestamp, first, _), (other_timestamp, line, _)) = (&pair[0], &pair[1]);
+        if timestamp == other_timestamp {
+            errors.push(LogError {
+                line: *line,
+                problem: Problem::DuplicateTimestamp { first: *first },
+            });
+        }
+    }
+
+    let mut on_duty: Option<GuardId> = None;
+    // Line where the guard on duty fell asleep
+    let mut asleep_since: Option<usize> = None;
+    for (_timestamp, line, action) in &records {
+        let problem = match action {
+            Action::BeginsShift(id) => {
+                if let (Some(id), Some(since)) = (on_duty, asleep_since.take()) {
+                    errors.push(LogError {
+                        line: since,
+                        problem: Problem::AsleepAtShiftEnd { id },
+                    });
+                }
+                on_duty = Some(*id);
+                None
+            }
+            _ if on_duty.is_none() => Some(Problem::NoGuardOnDuty),
+            Action::FallsAsleep => match asleep_since {
+                Some(since) => Some(Problem::FallsAsleepWhileAsleep { since }),
+                None => {
+                    asleep_since = Some(*line);
+                    None
+                }
+            },
+            Action::WakesUp => match asleep_since.take() {
+                Some(_since) => None,
+                None => Some(Problem::WakesUpWhileAwake),
+            },
+        };
+        if let Some(problem) = problem {
+            errors.push(LogError {
+                line: *line,
+                problem,
+            });
+        }
+    }
+    if let (Some(id), Some(since)) = (on_duty, asleep_since) {
+        errors.push(LogError {
+            line: since,
+            problem: Problem::AsleepAtShiftEnd { id },
+        });
+    }
+
+    if !errors.is_empty() {
+        errors.sort_by_key(|error| error.line);
+        return Err(errors);
+    }
+    Ok(records
+        .into_iter()
+        .map(|(timestamp, _line, action)| Line {
+            year: timestamp.date.year,
+            month: timestamp.date.month,
+            day: timestamp.date.day,
+            hour: timestamp.hour,
+            minute: timestamp.minute,
+            action,
+        })
+        .collect())
+}
+
+/// Strictly parse a `[YYYY-MM-DD HH:MM] action` log line, accepting only
+/// lines the `parse_line()` of every feature accepts.
+fn parse_record(text: &str) -> Result<(Timestamp, Action), Problem> {
+    let text = text.trim();
+    let (timestamp, action) = text
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once("] "))
+        .ok_or(Problem::Malformed)?;
+
+    let fields: Vec<&str> = timestamp.split(&['-', ' ', ':'][..]).collect();
+    let (year, month, day, hour, minute) = match fields.as_slice() {
+        [year, month, day, hour, minute] => (
+            digits(year, 4).ok_or(Problem::Malformed)?,
+            digits(month, 2).ok_or(Problem::Malformed)?,
+            digits(day, 2).ok_or(Problem::Malformed)?,
+            digits(hour, 2).ok_or(Problem::Malformed)?,
+            digits(minute, 2).ok_or(Problem::Malformed)?,
+        ),
+        _ => return Err(Problem::Malformed),
+    };
+    if !(1..=12).contains(&month)
+        || day == 0
+        || day > days_in_month(year, month)
+        || hour >= 24
+        || minute >= 60
+    {
+        return Err(Problem::InvalidTimestamp);
+    }
+    let timestamp = Timestamp {
+        date: Date { year, month, day },
+        hour,
+        minute,
+    };
+
+    let action = match action.trim() {
+        "wakes up" => Action::WakesUp,
+        "falls asleep" => Action::FallsAsleep,
+        other => other
+            .strip_prefix("Guard #")
+            .and_then(|rest| rest.strip_suffix(" begins shift"))
+            .filter(|id| !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|id| id.parse().ok())
+            .map(Action::BeginsShift)
+            .ok_or_else(|| Problem::UnknownAction(other.to_string()))?,
+    };
+
+    Ok((timestamp, action))
+}
+
+/// Value of `field` if made of exactly `width` ASCII digits
+fn digits(field: &str, width: usize) -> Option<u64> {
+    if field.len() == width && field.bytes().all(|b| b.is_ascii_digit()) {
+        field.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::init_logger;
+    use crate::validation::{validate, LogError, Problem};
+    use crate::{try_parse_input, PUZZLE_INPUT};
+
+    fn error(line: usize, problem: Problem) -> LogError {
+        LogError { line, problem }
+    }
+
+    #[test]
+    fn valid() {
+        init_logger();
+
+        assert_eq!(validate(PUZZLE_INPUT), vec![]);
+        assert!(try_parse_input(PUZZLE_INPUT).is_ok());
+    }
+
+    #[test]
+    fn sleep_records() {
+        init_logger();
+
+        let input = "[1518-11-01 00:05] falls asleep
+                     [1518-11-01 00:00] Guard #10 begins shift
+                     [1518-11-01 00:25] wakes up
+                     [1518-11-01 00:30] falls asleep
+                     [1518-11-01 00:40] falls asleep
+                     [1518-11-01 00:55] wakes up
+                     [1518-11-01 00:56] wakes up
+                     [1518-11-02 00:00] Guard #99 begins shift
+                     [1518-11-02 00:10] falls asleep
+                     [1518-11-03 00:00] Guard #10 begins shift
+                     [1518-11-03 00:10] falls asleep";
+        assert_eq!(
+            validate(input),
+            vec![
+                error(5, Problem::FallsAsleepWhileAsleep { since: 4 }),
+                error(7, Problem::WakesUpWhileAwake),
+                error(9, Problem::AsleepAtShiftEnd { id: 99 }),
+                error(11, Problem::AsleepAtShiftEnd { id: 10 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_lines() {
+        init_logger();
+
+        let input = "[1518-11-01 00:00] Guard #10 begins shift
+                     [1518-11-01 00:05] dozes off
+                     [1518-11-01 00:05] falls asleep
+                     [1518-11-01 00:25] wakes up
+                     [1518-11-31 00:00] Guard #10 begins shift
+                     1518-11-02 00:00 Guard #10 begins shift
+                     [1518-11-02 24:00] Guard #10 begins shift
+                     [1518-11-02 00:00] Guard #ten begins shift
+                     [1518-11-02 00:00] Guard #11 begins shift
+                     [1518-11-02 00:00] Guard #12 begins shift";
+        let errors = validate(input);
+        assert_eq!(
+            errors,
+            vec![
+                error(2, Problem::UnknownAction("dozes off".to_string())),
+                error(5, Problem::InvalidTimestamp),
+                error(6, Problem::Malformed),
+                error(7, Problem::InvalidTimestamp),
+                error(
+                    8,
+                    Problem::UnknownAction("Guard #ten begins shift".to_string())
+                ),
+                error(10, Problem::DuplicateTimestamp { first: 9 }),
+            ]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "line 2: unknown action \"dozes off\""
+        );
+        assert_eq!(try_parse_input(input), Err(errors));
+    }
+
+    #[test]
+    fn stricter_than_parse_line() {
+        init_logger();
+
+        let input = "[1518-11-1 00:00] Guard #10 begins shift
+                     [1518-11-01 00:00] Guard #-5 begins shift
+                     [15180-11-01 00:00] Guard #10 begins shift
+                     [1518-11-01 0:00] Guard #10 begins shift
+                     [1518-11-01 00:00] Guard #+5 begins shift
+                     [1518-11-01 00:00] Guard #99999999999999999999 begins shift";
+        let errors = validate(input);
+        assert_eq!(
+            errors,
+            vec![
+                error(1, Problem::Malformed),
+                error(
+                    2,
+                    Problem::UnknownAction("Guard #-5 begins shift".to_string())
+                ),
+                error(3, Problem::Malformed),
+                error(4, Problem::Malformed),
+                error(
+                    5,
+                    Problem::UnknownAction("Guard #+5 begins shift".to_string())
+                ),
+                error(
+                    6,
+                    Problem::UnknownAction("Guard #99999999999999999999 begins shift".to_string())
+                ),
+            ]
+        );
+        for line in input.lines() {
+            assert!(try_parse_input(line).is_err());
+        }
+    }
+
+    #[test]
+    fn no_guard_on_duty() {
+        init_logger();
+
+        let input = "[1518-11-01 00:05] falls asleep
+                     [1518-11-01 00:25] wakes up";
+        assert_eq!(
+            validate(input),
+            vec![
+                error(1, Problem::NoGuardOnDuty),
+                error(2, Problem::NoGuardOnDuty),
+            ]
+        );
+    }
+}
@@ -7,6 +7,10 @@ use criterion::Criterion;
 use criterion::{Bencher, Fun};
 
 use day05::benchmark::to_benchmark;
+use day05::engine::generate_polymer;
+#[cfg(feature = "parallel")]
+use day05::Day05ParallelStack;
+use day05::{AoC, Day05Stack};
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
     let functions: Vec<_> = to_benchmark()
@@ -36,9 +40,32 @@ fn criterion_benchmark_part2(c: &mut Criterion) {
     c.bench_functions("day05_part2", functions, ());
 }
 
+fn criterion_benchmark_part1_generated(c: &mut Criterion) {
+    // Multi-megabyte polymer, to see how part 1 itself scales
+    let polymer: &'static str = Box::leak(generate_polymer(8 * 1024 * 1024, 2018).into_boxed_str());
+
+    let stack = Day05Stack::new(polymer);
+    #[cfg(feature = "parallel")]
+    let parallel_stack = Day05ParallelStack::new(polymer);
+    let functions = vec![
+        Fun::new(
+            &stack.description().replace(" ", "_"),
+            move |b: &mut Bencher, _: &()| b.iter(|| stack.solution_part1()),
+        ),
+        #[cfg(feature = "parallel")]
+        Fun::new(
+            &parallel_stack.description().replace(" ", "_"),
+            move |b: &mut Bencher, _: &()| b.iter(|| parallel_stack.solution_part1()),
+        ),
+    ];
+
+    c.bench_functions("day05_part1_generated", functions, ());
+}
+
 criterion_group!(
     benches,
     criterion_benchmark_part1,
-    criterion_benchmark_part2
+    criterion_benchmark_part2,
+    criterion_benchmark_part1_generated
 );
 criterion_main!(benches);
@@ -0,0 +1,292 @@
+use std::collections::HashSet;
+
+/// Table of the pairs of units annihilating each other.
+///
+/// Rules are directed: a rule `(first, second)` only fires when `first` is
+/// directly followed by `second`. ASCII pairs are looked up in a bit table,
+/// other pairs in a hash set.
+#[derive(Debug, Clone)]
+pub struct ReactionRules {
+    /// `ascii[first]` bit `second` is set if `first` followed by `second` reacts
+    ascii: Vec<u128>,
+    other: HashSet<(char, char)>,
+}
+
+impl ReactionRules {
+    /// Rules where nothing reacts.
+    pub fn new() -> ReactionRules {
+        ReactionRules {
+            ascii: vec![0; 128],
+            other: HashSet::new(),
+        }
+    }
+
+    /// The puzzle's rules: an ASCII letter reacts with the same letter of the
+    /// opposite case, in either order.
+    pub fn case_pairs() -> ReactionRules {
+        let mut rules = ReactionRules::new();
+        for lower in b'a'..=b'z' {
+            rules.add_symmetric(lower as char, lower.to_ascii_uppercase() as char);
+        }
+        rules
+    }
+
+    /// Make `first` react when directly followed by `second`.
+    pub fn add(&mut self, first: char, second: char) -> &mut ReactionRules {
+        if first.is_ascii() && second.is_ascii() {
+            self.ascii[first as usize] |= 1 << (second as u32);
+        } else {
+            self.other.insert((first, second));
+        }
+        self
+    }
+
+    /// Make `a` and `b` react when next to each other, in either order.
+    pub fn add_symmetric(&mut self, a: char, b: char) -> &mut ReactionRules {
+        self.add(a, b).add(b, a)
+    }
+
+    /// Whether `first` directly followed by `second` reacts.
+    pub fn reacts(&self, first: char, second: char) -> bool {
+        if first.is_ascii() && second.is_ascii() {
+            self.ascii[first as usize] & (1 << (second as u32)) != 0
+        } else {
+            self.other.contains(&(first, second))
+        }
+    }
+}
+
+impl Default for ReactionRules {
+    fn default() -> ReactionRules {
+        ReactionRules::case_pairs()
+    }
+}
+
+/// Polymer reduction following a [`ReactionRules`] table.
+///
+/// Units are pushed on a stack of unreacted units; a unit reacting with the
+/// top of the stack pops it instead. This gives the fully reacted polymer in
+/// a single pass.
+///
+/// Reactions always happen leftmost first. With rules where the order of the
+/// reactions matters, that is one possible outcome among others: with rules
+/// `ab` and `bc`, `"abc"` reduces to `"c"`, where reacting `bc` first would
+/// leave `"a"`. The result is only canonical for confluent rules, like
+/// `ReactionRules::case_pairs()`.
+#[derive(Debug, Clone, Default)]
+pub struct PolymerEngine {
+    rules: ReactionRules,
+}
+
+impl PolymerEngine {
+    pub fn new(rules: ReactionRules) -> PolymerEngine {
+        PolymerEngine { rules }
+    }
+
+    pub fn rules(&self) -> &ReactionRules {
+        &self.rules
+    }
+
+    /// Add `unit` after the unreacted units of `stack`.
+    pub fn react(&self, stack: &mut Vec<char>, unit: char) {
+        match stack.last() {
+            Some(&last) if self.rules.reacts(last, unit) => {
+                stack.pop();
+            }
+            _ => stack.push(unit),
+        }
+    }
+
+    /// Units left once `units` fully reacted, reducing the stack left to
+    /// right (see [`PolymerEngine`] for rules that are not confluent).
+    pub fn reduce(&self, units: impl IntoIterator<Item = char>) -> Vec<char> {
+        let units = units.into_iter();
+        let mut stack = Vec::with_capacity(units.size_hint().0);
+        for unit in units {
+            self.react(&mut stack, unit);
+        }
+        stack
+    }
+
+    /// Number of units of `right` cancelled with the end of `left` when the
+    /// reduced polymers `left` and `right` are joined.
+    ///
+    /// Reduced polymers never react internally, so only the junction is
+    /// looked at: this stops at the first pair that does not react.
+    pub fn junction_len(&self, left: &[char], right: &[char]) -> usize {
+        left.iter()
+            .rev()
+            .zip(right)
+            .take_while(|&(&last, &unit)| self.rules.reacts(last, unit))
+            .count()
+    }
+
+    /// Units left once the reduced polymers `left` and `right` are joined and
+    /// fully reacted.
+    ///
+    /// Only the units at the junction can react, so this costs the number of
+    /// units cancelled plus copying the rest of `right`. The result is the same
+    /// as reducing `left` followed by `right` as a whole; splitting a polymer,
+    /// reducing the parts and merging them gives the same result as reducing it
+    /// whole as long as the rules are confluent, like
+    /// `ReactionRules::case_pairs()`.
+    pub fn merge(&self, mut left: Vec<char>, right: Vec<char>) -> Vec<char> {
+        if left.is_empty() {
+            return right;
+        }
+        let cancelled = self.junction_len(&left, &right);
+        left.truncate(left.len() - cancelled);
+        left.extend_from_slice(&right[cancelled..]);
+        left
+    }
+
+    /// Number of units left once `units` fully reacted.
+    pub fn reduced_len(&self, units: impl IntoIterator<Item = char>) -> usize {
+        self.reduce(units).len()
+    }
+}
+
+/// Pseudo-random polymer of `len` ASCII letters for benchmarks, the same for
+/// a given `seed`.
+///
+/// Units often follow their opposite so that large parts of it react.
+pub fn generate_polymer(len: usize, seed: u64) -> String {
+    // xorshift64*, the seed must not be zero
+    let mut state = (seed << 1) | 1;
+    let mut next = move || {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 32
+    };
+
+    let mut polymer = String::with_capacity(len);
+    let mut previous = b'a';
+    for _ in 0..len {
+        let random = next();
+        let unit = if random % 3 == 0 {
+            // Opposite of the previous unit
+            previous ^ 0x20
+        } else {
+            let letter = b'a' + (random / 3 % 26) as u8;
+            if random / 78 % 2 == 0 {
+                letter
+            } else {
+                letter.to_ascii_uppercase()
+            }
+        };
+        polymer.push(unit as char);
+        previous = unit;
+    }
+    polymer
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{generate_polymer, PolymerEngine, ReactionRules};
+    use crate::tests::init_logger;
+
+    fn reduce(engine: &PolymerEngine, polymer: &str) -> String {
+        engine.reduce(polymer.chars()).into_iter().collect()
+    }
+
+    #[test]
+    fn case_pairs() {
+        init_logger();
+
+        let engine = PolymerEngine::default();
+        assert_eq!(reduce(&engine, "dabAcCaCBAcCcaDA"), "dabCBAcaDA");
+        assert_eq!(reduce(&engine, "aA"), "");
+        assert_eq!(reduce(&engine, "abBA"), "");
+        assert_eq!(reduce(&engine, "abAB"), "abAB");
+        assert_eq!(reduce(&engine, "aabAAB"), "aabAAB");
+        // Only ASCII letters react
+        assert_eq!(reduce(&engine, "1!éÉ"), "1!éÉ");
+    }
+
+    #[test]
+    fn asymmetric_and_non_ascii() {
+        init_logger();
+
+        let mut rules = ReactionRules::new();
+        rules.add('a', 'b').add('→', '←').add_symmetric('α', 'Ω');
+        let engine = PolymerEngine::new(rules);
+
+        assert!(engine.rules().reacts('a', 'b'));
+        assert!(!engine.rules().reacts('b', 'a'));
+        assert!(!engine.rules().reacts('a', 'A'));
+
+        assert_eq!(reduce(&engine, "ab"), "");
+        assert_eq!(reduce(&engine, "ba"), "ba");
+        assert_eq!(reduce(&engine, "→←←→"), "←→");
+        assert_eq!(reduce(&engine, "αaΩbΩα"), "αaΩb");
+        assert_eq!(reduce(&engine, "xαΩy"), "xy");
+        assert_eq!(reduce(&engine, "xΩαy"), "xy");
+        assert_eq!(engine.reduced_len("aαΩb".chars()), 0);
+    }
+
+    #[test]
+    fn leftmost_first() {
+        init_logger();
+
+        // Not confluent: reacting `bc` first would leave "a"
+        let mut rules = ReactionRules::new();
+        rules.add('a', 'b').add('b', 'c');
+        let engine = PolymerEngine::new(rules);
+        assert_eq!(reduce(&engine, "abc"), "c");
+    }
+
+    #[test]
+    fn no_rules() {
+        init_logger();
+
+        let engine = PolymerEngine::new(ReactionRules::new());
+        assert_eq!(engine.reduced_len("dabAcCaCBAcCcaDA".chars()), 16);
+    }
+
+    #[test]
+    fn merge() {
+        init_logger();
+
+        let engine = PolymerEngine::default();
+        let polymer = "dabAcCaCBAcCcaDA";
+        for split in 0..=polymer.len() {
+            let (left, right) = polymer.split_at(split);
+            let merged = engine.merge(engine.reduce(left.chars()), engine.reduce(right.chars()));
+            assert_eq!(merged.into_iter().collect::<String>(), "dabCBAcaDA");
+        }
+    }
+
+    #[test]
+    fn merge_junction_only() {
+        init_logger();
+
+        let engine = PolymerEngine::default();
+        let left: Vec<char> = "xyzabc".chars().collect();
+        let mut right = vec!['q'; 1_000_003];
+        right[..3].copy_from_slice(&['C', 'B', 'A']);
+        assert_eq!(engine.junction_len(&left, &right), 3);
+        let merged = engine.merge(left, right);
+        assert_eq!(merged.len(), 3 + 1_000_000);
+        assert_eq!(merged[..4], ['x', 'y', 'z', 'q']);
+
+        // Units past the junction are not reacted again
+        let merged = engine.merge(vec!['a'], vec!['A', 'b', 'B']);
+        assert_eq!(merged, vec!['b', 'B']);
+        assert_eq!(engine.junction_len(&['a'], &['b', 'B']), 0);
+    }
+
+    #[test]
+    fn generated() {
+        init_logger();
+
+        let polymer = generate_polymer(10_000, 42);
+        assert_eq!(polymer.len(), 10_000);
+        assert!(polymer.chars().all(|unit| unit.is_ascii_alphabetic()));
+        assert_eq!(polymer, generate_polymer(10_000, 42));
+        assert_ne!(polymer, generate_polymer(10_000, 43));
+
+        let reduced = PolymerEngine::default().reduced_len(polymer.chars());
+        assert!(reduced < polymer.len() / 2, "reduced to {}", reduced);
+    }
+}
@@ -81,6 +81,7 @@
 // #[macro_use]
 // extern crate pretty_assertions;
 
+#[cfg(feature = "parallel")]
 extern crate rayon;
 
 use std::fmt::Debug;
@@ -88,7 +89,9 @@ use std::fmt::Debug;
 pub mod initial;
 pub use initial::Day05Initial;
 
+#[cfg(feature = "parallel")]
 pub mod initial_parallel_part2;
+#[cfg(feature = "parallel")]
 pub use initial_parallel_part2::Day05InitialParallelPart2;
 
 pub mod explicit_loop;
@@ -100,9 +103,22 @@ pub use iter_fold::Day05IteratorFold;
 pub mod iter_scan;
 pub use iter_scan::Day05IteratorScan;
 
+pub mod engine;
+
 pub mod stack;
 pub use stack::Day05Stack;
 
+pub mod reduced_once;
+pub use reduced_once::Day05ReducedOnce;
+
+pub mod streaming;
+pub use streaming::Day05Streaming;
+
+#[cfg(feature = "parallel")]
+pub mod parallel_stack;
+#[cfg(feature = "parallel")]
+pub use parallel_stack::Day05ParallelStack;
+
 type Day05SolutionPart1 = usize;
 type Day05SolutionPart2 = usize;
 
@@ -142,11 +158,16 @@ pub mod benchmark {
     pub fn to_benchmark<'a>() -> BenchmarkVector<'a> {
         vec![
             Box::new(Day05Initial::new(PUZZLE_INPUT)),
+            #[cfg(feature = "parallel")]
             Box::new(Day05InitialParallelPart2::new(PUZZLE_INPUT)),
             Box::new(Day05ExplicitLoop::new(PUZZLE_INPUT)),
             Box::new(Day05IteratorFold::new(PUZZLE_INPUT)),
             Box::new(Day05IteratorScan::new(PUZZLE_INPUT)),
             Box::new(Day05Stack::new(PUZZLE_INPUT)),
+            #[cfg(feature = "parallel")]
+            Box::new(Day05ParallelStack::new(PUZZLE_INPUT)),
+            Box::new(Day05Streaming::new(PUZZLE_INPUT)),
+            Box::new(Day05ReducedOnce::new(PUZZLE_INPUT)),
         ]
     }
 }
@@ -0,0 +1,202 @@
+use rayon::prelude::*;
+
+use crate::engine::PolymerEngine;
+use crate::{AoC, Day05SolutionPart1, Day05SolutionPart2};
+
+/// Polymers shorter than this (in bytes) are reduced on a single thread
+pub const DEFAULT_CHUNK_LEN: usize = 64 * 1024;
+
+/// Stack reduction of a single polymer split across threads.
+///
+/// The polymer is halved until the halves are short enough, each half is
+/// reduced to a stack of unreacted units and the stacks are merged back
+/// pairwise, see `PolymerEngine::merge()`.
+#[derive(Debug)]
+pub struct Day05ParallelStack<'a> {
+    input: &'a str,
+    chunk_len: usize,
+    engine: PolymerEngine,
+}
+
+impl<'a> Day05ParallelStack<'a> {
+    /// Solution reducing on a single thread polymers shorter than `chunk_len`
+    /// bytes.
+    pub fn with_chunk_len(input: &'a str, chunk_len: usize) -> Day05ParallelStack<'a> {
+        assert!(chunk_len > 0, "Chunks can't be empty");
+        Day05ParallelStack {
+            input: input.trim(),
+            chunk_len,
+            engine: PolymerEngine::default(),
+        }
+    }
+
+    /// Units left once the input without the units rejected by `keep` fully
+    /// reacted.
+    pub fn reduce<F>(&self, keep: F) -> Vec<char>
+    where
+        F: Fn(char) -> bool + Sync,
+    {
+        reduce(&self.engine, self.input, self.chunk_len, &keep)
+    }
+}
+
+fn reduce<F>(engine: &PolymerEngine, polymer: &str, chunk_len: usize, keep: &F) -> Vec<char>
+where
+    F: Fn(char) -> bool + Sync,
+{
+    let mut middle = polymer.len() / 2;
+    while !polymer.is_char_boundary(middle) {
+        middle += 1;
+    }
+    // Also stop at a single multi-byte unit
+    if polymer.len() <= chunk_len || middle == polymer.len() {
+        return engine.reduce(polymer.chars().filter(|&unit| keep(unit)));
+    }
+
+    let (left, right) = polymer.split_at(middle);
+    let (left, right) = rayon::join(
+        || reduce(engine, left, chunk_len, keep),
+        || reduce(engine, right, chunk_len, keep),
+    );
+    engine.merge(left, right)
+}
+
+impl<'a> AoC<'a> for Day05ParallelStack<'a> {
+    type SolutionPart1 = Day05SolutionPart1;
+    type SolutionPart2 = Day05SolutionPart2;
+
+    fn description(&self) -> &'static str {
+        "Parallel divide and conquer stack"
+    }
+
+    fn new(input: &'a str) -> Day05ParallelStack<'a> {
+        Day05ParallelStack::with_chunk_len(input, DEFAULT_CHUNK_LEN)
+    }
+
+    fn solution_part1(&self) -> Self::SolutionPart1 {
+        self.reduce(|_unit| true).len()
+    }
+
+    fn solution_part2(&self) -> Self::SolutionPart2 {
+        "abcdefghijklmnopqrstuvwxyz"
+            .par_chars()
+            .map(|c| self.reduce(|unit| unit.to_ascii_lowercase() != c).len())
+            .min()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod part1 {
+        mod solution {
+            use super::super::super::Day05ParallelStack;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 9296;
+                let to_check = Day05ParallelStack::new(PUZZLE_INPUT).solution_part1();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day05ParallelStack;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex04_solution() {
+                init_logger();
+
+                let expected = 10;
+                let input = "dabAcCaCBAcCcaDA";
+                let to_check = Day05ParallelStack::new(input).solution_part1();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod extra {
+            use super::super::super::Day05ParallelStack;
+            use crate::engine::{generate_polymer, PolymerEngine};
+            use crate::{tests::init_logger, AoC, Day05Stack, PUZZLE_INPUT};
+
+            #[test]
+            fn small_chunks() {
+                init_logger();
+
+                for chunk_len in 1..20 {
+                    let solution =
+                        Day05ParallelStack::with_chunk_len("dabAcCaCBAcCcaDA", chunk_len);
+                    let reduced: String = solution.reduce(|_unit| true).into_iter().collect();
+                    assert_eq!(reduced, "dabCBAcaDA");
+                }
+
+                let to_check =
+                    Day05ParallelStack::with_chunk_len(PUZZLE_INPUT, 100).solution_part1();
+                assert_eq!(to_check, 9296);
+            }
+
+            #[test]
+            fn non_ascii_chunk_boundaries() {
+                init_logger();
+
+                let solution = Day05ParallelStack::with_chunk_len("éaAéxX", 1);
+                let reduced: String = solution.reduce(|_unit| true).into_iter().collect();
+                assert_eq!(reduced, "éé");
+            }
+
+            #[test]
+            fn generated() {
+                init_logger();
+
+                let polymer = generate_polymer(4 * 1024 * 1024, 2018);
+                let expected = PolymerEngine::default().reduce(polymer.chars());
+                let to_check = Day05ParallelStack::new(&polymer).reduce(|_unit| true);
+
+                assert_eq!(to_check, expected);
+                assert_eq!(
+                    Day05ParallelStack::new(&polymer).solution_part1(),
+                    Day05Stack::new(&polymer).solution_part1()
+                );
+            }
+        }
+    }
+
+    mod part2 {
+        mod solution {
+            use super::super::super::Day05ParallelStack;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 5534;
+                let to_check = Day05ParallelStack::new(PUZZLE_INPUT).solution_part2();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day05ParallelStack;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = 4;
+                let input = "dabAcCaCBAcCcaDA";
+                let to_check = Day05ParallelStack::with_chunk_len(input, 3).solution_part2();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+    }
+}
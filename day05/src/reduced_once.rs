@@ -0,0 +1,187 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::engine::PolymerEngine;
+use crate::{AoC, Day05SolutionPart1, Day05SolutionPart2};
+
+/// Shortest polymer obtained by removing all units of a single type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Removal {
+    /// Removed unit type, as a lowercase letter
+    pub unit: char,
+    /// Length of the polymer fully reacted after the removal
+    pub len: usize,
+    /// How many units shorter than the fully reacted polymer without removal
+    pub shrink: usize,
+}
+
+/// Part 2 starting from the part 1 reduction.
+///
+/// Removing units from a fully reacted polymer and reacting it again gives
+/// the same length as removing them from the original one, since the
+/// reactions already done only involved pairs of a same type. The polymer is
+/// thus reduced once, and the 26 removals run on the much shorter result.
+#[derive(Debug)]
+pub struct Day05ReducedOnce<'a> {
+    input: &'a str,
+    engine: PolymerEngine,
+}
+
+impl<'a> Day05ReducedOnce<'a> {
+    /// Best unit type to remove (first in alphabetical order on ties).
+    pub fn best_removal(&self) -> Removal {
+        let reduced = self.engine.reduce(self.input.chars());
+        let units = "abcdefghijklmnopqrstuvwxyz";
+        #[cfg(feature = "parallel")]
+        let units = units.par_chars();
+        #[cfg(not(feature = "parallel"))]
+        let units = units.chars();
+        let (len, unit) = units
+            .map(|c| {
+                let len = self.engine.reduced_len(
+                    reduced
+                        .iter()
+                        .cloned()
+                        .filter(|i| i.to_ascii_lowercase() != c),
+                );
+                (len, c)
+            })
+            .min()
+            .unwrap();
+        Removal {
+            unit,
+            len,
+            shrink: reduced.len() - len,
+        }
+    }
+}
+
+impl<'a> AoC<'a> for Day05ReducedOnce<'a> {
+    type SolutionPart1 = Day05SolutionPart1;
+    type SolutionPart2 = Day05SolutionPart2;
+
+    fn description(&self) -> &'static str {
+        "Stack reusing part 1 reduction"
+    }
+
+    fn new(input: &'a str) -> Day05ReducedOnce<'a> {
+        Day05ReducedOnce {
+            input: input.trim(),
+            engine: PolymerEngine::default(),
+        }
+    }
+
+    fn solution_part1(&self) -> Self::SolutionPart1 {
+        self.engine.reduced_len(self.input.chars())
+    }
+
+    fn solution_part2(&self) -> Self::SolutionPart2 {
+        self.best_removal().len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod part1 {
+        mod solution {
+            use super::super::super::Day05ReducedOnce;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 9296;
+                let to_check = Day05ReducedOnce::new(PUZZLE_INPUT).solution_part1();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day05ReducedOnce;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex04_solution() {
+                init_logger();
+
+                let expected = 10;
+                let input = "dabAcCaCBAcCcaDA";
+                let to_check = Day05ReducedOnce::new(input).solution_part1();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+    }
+
+    mod part2 {
+        mod solution {
+            use super::super::super::Day05ReducedOnce;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 5534;
+                let to_check = Day05ReducedOnce::new(PUZZLE_INPUT).solution_part2();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day05ReducedOnce;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = 4;
+                let input = "dabAcCaCBAcCcaDA";
+                let to_check = Day05ReducedOnce::new(input).solution_part2();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod extra {
+            use super::super::super::{Day05ReducedOnce, Removal};
+            use crate::engine::generate_polymer;
+            use crate::{tests::init_logger, AoC, Day05Stack, PUZZLE_INPUT};
+
+            #[test]
+            fn best_removal() {
+                init_logger();
+
+                let to_check = Day05ReducedOnce::new("dabAcCaCBAcCcaDA").best_removal();
+                assert_eq!(
+                    to_check,
+                    Removal {
+                        unit: 'c',
+                        len: 4,
+                        shrink: 6,
+                    }
+                );
+
+                let to_check = Day05ReducedOnce::new(PUZZLE_INPUT).best_removal();
+                assert_eq!((to_check.len, to_check.shrink), (5534, 9296 - 5534));
+            }
+
+            #[test]
+            fn same_as_stack() {
+                init_logger();
+
+                for seed in 0..10 {
+                    let polymer = generate_polymer(10_000, seed);
+                    assert_eq!(
+                        Day05ReducedOnce::new(&polymer).solution_part2(),
+                        Day05Stack::new(&polymer).solution_part2()
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,23 @@
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+use crate::engine::{PolymerEngine, ReactionRules};
 use crate::{AoC, Day05SolutionPart1, Day05SolutionPart2};
 
 #[derive(Debug)]
 pub struct Day05Stack<'a> {
     input: &'a str,
+    engine: PolymerEngine,
+}
+
+impl<'a> Day05Stack<'a> {
+    /// Solution reacting units following `rules` instead of the puzzle's.
+    pub fn with_rules(input: &'a str, rules: ReactionRules) -> Day05Stack<'a> {
+        Day05Stack {
+            input: input.trim(),
+            engine: PolymerEngine::new(rules),
+        }
+    }
 }
 
 impl<'a> AoC<'a> for Day05Stack<'a> {
@@ -16,47 +29,29 @@ impl<'a> AoC<'a> for Day05Stack<'a> {
     }
 
     fn new(input: &'a str) -> Day05Stack {
-        Day05Stack {
-            input: input.trim(),
-        }
+        Day05Stack::with_rules(input, ReactionRules::case_pairs())
     }
 
     fn solution_part1(&self) -> Self::SolutionPart1 {
-        stack(self.input.chars(), self.input.len())
+        self.engine.reduced_len(self.input.chars())
     }
 
     fn solution_part2(&self) -> Self::SolutionPart2 {
-        let input_len = self.input.len();
-        "abcdefghijklmnopqrstuvwxyz"
-            .par_chars()
+        let units = "abcdefghijklmnopqrstuvwxyz";
+        #[cfg(feature = "parallel")]
+        let units = units.par_chars();
+        #[cfg(not(feature = "parallel"))]
+        let units = units.chars();
+        units
             .map(|c| {
-                stack(
-                    self.input.chars().filter(|i| i.to_ascii_lowercase() != c),
-                    input_len,
-                )
+                self.engine
+                    .reduced_len(self.input.chars().filter(|i| i.to_ascii_lowercase() != c))
             })
             .min()
             .unwrap()
     }
 }
 
-fn stack(input: impl Iterator<Item = char>, len: usize) -> Day05SolutionPart1 {
-    let mut stack: Vec<char> = Vec::with_capacity(len);
-
-    for c in input {
-        if !stack.is_empty()
-            && stack[stack.len() - 1] != c
-            && stack[stack.len() - 1].to_ascii_lowercase() == c.to_ascii_lowercase()
-        {
-            stack.pop();
-        } else {
-            stack.push(c);
-        }
-    }
-
-    stack.len()
-}
-
 #[cfg(test)]
 mod tests {
     mod part1 {
@@ -91,11 +86,23 @@ mod tests {
             }
         }
 
-        /*
         mod extra {
-            use ::*;
+            use super::super::super::Day05Stack;
+            use crate::engine::ReactionRules;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn custom_rules() {
+                init_logger();
+
+                let mut rules = ReactionRules::new();
+                rules.add('a', 'A').add_symmetric('→', '←');
+                let to_check = Day05Stack::with_rules("dabAcCaCBAaA→←", rules).solution_part1();
+
+                // Only "aA" and "→←" react
+                assert_eq!(to_check, 10);
+            }
         }
-        */
     }
 
     mod part2 {
@@ -0,0 +1,305 @@
+use std::io::{self, Read, Write};
+use std::str;
+
+use crate::engine::PolymerEngine;
+use crate::{AoC, Day05SolutionPart1, Day05SolutionPart2};
+
+/// Number of bytes read at once by default
+pub const DEFAULT_BUFFER_LEN: usize = 64 * 1024;
+
+/// Polymer reduction from any reader, keeping only the unreacted units.
+///
+/// The polymer is read in chunks of `buffer_len` bytes and decoded as UTF-8,
+/// units split across chunks included. Whitespace is skipped, so trailing
+/// newlines or a polymer wrapped over lines are fine.
+#[derive(Debug, Clone)]
+pub struct StreamReducer {
+    engine: PolymerEngine,
+    buffer_len: usize,
+}
+
+impl StreamReducer {
+    pub fn new(engine: PolymerEngine) -> StreamReducer {
+        StreamReducer::with_buffer_len(engine, DEFAULT_BUFFER_LEN)
+    }
+
+    pub fn with_buffer_len(engine: PolymerEngine, buffer_len: usize) -> StreamReducer {
+        // A UTF-8 encoded char takes up to 4 bytes
+        assert!(buffer_len >= 4, "Buffer too small: {}", buffer_len);
+        StreamReducer { engine, buffer_len }
+    }
+
+    pub fn engine(&self) -> &PolymerEngine {
+        &self.engine
+    }
+
+    /// Units left once the polymer read from `input` fully reacted.
+    pub fn reduce<R: Read>(&self, input: R) -> io::Result<Vec<char>> {
+        self.reduce_filtered(input, |_unit| true)
+    }
+
+    /// Number of units left once the polymer read from `input` fully reacted.
+    pub fn reduced_len<R: Read>(&self, input: R) -> io::Result<usize> {
+        self.reduce(input).map(|stack| stack.len())
+    }
+
+    /// Write the polymer read from `input`, fully reacted, to `out` and return
+    /// its number of units.
+    pub fn write_reduced<R: Read, W: Write>(&self, input: R, mut out: W) -> io::Result<usize> {
+        let stack = self.reduce(input)?;
+        let mut encoded = [0; 4];
+        for chunk in stack.chunks(self.buffer_len) {
+            let mut buffer = Vec::with_capacity(chunk.len());
+            for unit in chunk {
+                buffer.extend_from_slice(unit.encode_utf8(&mut encoded).as_bytes());
+            }
+            out.write_all(&buffer)?;
+        }
+        out.flush()?;
+        Ok(stack.len())
+    }
+
+    /// Like `reduce()`, dropping the units rejected by `keep` before they react.
+    pub fn reduce_filtered<R, F>(&self, mut input: R, keep: F) -> io::Result<Vec<char>>
+    where
+        R: Read,
+        F: Fn(char) -> bool,
+    {
+        let mut stack = Vec::new();
+        let mut buffer = vec![0; self.buffer_len];
+        // Bytes at the start of `buffer` left from the previous read: the
+        // beginning of a unit split across reads
+        let mut pending = 0;
+        loop {
+            let read = match input.read(&mut buffer[pending..]) {
+                Ok(read) => read,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if read == 0 {
+                if pending > 0 {
+                    return Err(invalid_data("polymer ends in the middle of a unit"));
+                }
+                return Ok(stack);
+            }
+
+            let filled = pending + read;
+            let valid = match str::from_utf8(&buffer[..filled]) {
+                Ok(_) => filled,
+                // Incomplete unit at the end, completed by the next read
+                Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                Err(e) => return Err(invalid_data(e)),
+            };
+            let units = str::from_utf8(&buffer[..valid]).unwrap();
+            for unit in units.chars() {
+                if !unit.is_whitespace() && keep(unit) {
+                    self.engine.react(&mut stack, unit);
+                }
+            }
+
+            buffer.copy_within(valid..filled, 0);
+            pending = filled - valid;
+        }
+    }
+}
+
+impl Default for StreamReducer {
+    fn default() -> StreamReducer {
+        StreamReducer::new(PolymerEngine::default())
+    }
+}
+
+fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Stack reduction streaming the input through a `StreamReducer`.
+#[derive(Debug)]
+pub struct Day05Streaming<'a> {
+    input: &'a str,
+    reducer: StreamReducer,
+}
+
+impl<'a> AoC<'a> for Day05Streaming<'a> {
+    type SolutionPart1 = Day05SolutionPart1;
+    type SolutionPart2 = Day05SolutionPart2;
+
+    fn description(&self) -> &'static str {
+        "Streaming stack"
+    }
+
+    fn new(input: &'a str) -> Day05Streaming<'a> {
+        Day05Streaming {
+            input,
+            reducer: StreamReducer::default(),
+        }
+    }
+
+    fn solution_part1(&self) -> Self::SolutionPart1 {
+        self.reducer.reduced_len(self.input.as_bytes()).unwrap()
+    }
+
+    fn solution_part2(&self) -> Self::SolutionPart2 {
+        "abcdefghijklmnopqrstuvwxyz"
+            .chars()
+            .map(|c| {
+                self.reducer
+                    .reduce_filtered(self.input.as_bytes(), |unit| unit.to_ascii_lowercase() != c)
+                    .unwrap()
+                    .len()
+            })
+            .min()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod part1 {
+        mod solution {
+            use super::super::super::Day05Streaming;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 9296;
+                let to_check = Day05Streaming::new(PUZZLE_INPUT).solution_part1();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day05Streaming;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex04_solution() {
+                init_logger();
+
+                let expected = 10;
+                let input = "dabAcCaCBAcCcaDA\n";
+                let to_check = Day05Streaming::new(input).solution_part1();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod extra {
+            use std::io::{self, Read};
+
+            use crate::engine::{generate_polymer, PolymerEngine, ReactionRules};
+            use crate::streaming::StreamReducer;
+            use crate::tests::init_logger;
+
+            /// Reader returning at most one byte per read
+            struct ByteByByte<'a>(&'a [u8]);
+
+            impl<'a> Read for ByteByByte<'a> {
+                fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                    match self.0.split_first() {
+                        Some((&byte, rest)) if !buf.is_empty() => {
+                            buf[0] = byte;
+                            self.0 = rest;
+                            Ok(1)
+                        }
+                        _ => Ok(0),
+                    }
+                }
+            }
+
+            #[test]
+            fn write_reduced() {
+                init_logger();
+
+                let mut out = Vec::new();
+                let len = StreamReducer::default()
+                    .write_reduced("dabAcCaC\nBAcCcaDA\n".as_bytes(), &mut out)
+                    .unwrap();
+
+                assert_eq!(len, 10);
+                assert_eq!(String::from_utf8(out).unwrap(), "dabCBAcaDA");
+            }
+
+            #[test]
+            fn units_split_across_reads() {
+                init_logger();
+
+                let mut rules = ReactionRules::new();
+                rules.add_symmetric('é', 'É').add('→', '←');
+                let input = "xéÉ→←€é";
+                for buffer_len in 4..10 {
+                    let reducer = StreamReducer::with_buffer_len(
+                        PolymerEngine::new(rules.clone()),
+                        buffer_len,
+                    );
+                    let reduced = reducer.reduce(input.as_bytes()).unwrap();
+                    assert_eq!(reduced.into_iter().collect::<String>(), "x€é");
+                    let reduced = reducer.reduce(ByteByByte(input.as_bytes())).unwrap();
+                    assert_eq!(reduced.into_iter().collect::<String>(), "x€é");
+                }
+            }
+
+            #[test]
+            fn invalid_utf8() {
+                init_logger();
+
+                let reducer = StreamReducer::default();
+                let error = reducer.reduce(&b"aA\xffbB"[..]).unwrap_err();
+                assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+                // Truncated "é"
+                let error = reducer.reduce(&b"aA\xc3"[..]).unwrap_err();
+                assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+            }
+
+            #[test]
+            fn generated() {
+                init_logger();
+
+                let polymer = generate_polymer(1024 * 1024, 5);
+                let expected = PolymerEngine::default().reduce(polymer.chars());
+                let reducer = StreamReducer::with_buffer_len(PolymerEngine::default(), 1000);
+
+                assert_eq!(reducer.reduce(polymer.as_bytes()).unwrap(), expected);
+            }
+        }
+    }
+
+    mod part2 {
+        mod solution {
+            use super::super::super::Day05Streaming;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 5534;
+                let to_check = Day05Streaming::new(PUZZLE_INPUT).solution_part2();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day05Streaming;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = 4;
+                let input = "dabAcCaCBAcCcaDA";
+                let to_check = Day05Streaming::new(input).solution_part2();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{find_bounding_box, manhattan_distance, Position, Result};
+use crate::{parse_input, AoC, Day06SolutionPart1, Day06SolutionPart2};
+
+#[derive(Debug)]
+pub struct Day06Direct<'a> {
+    input: &'a str,
+}
+
+impl<'a> AoC<'a> for Day06Direct<'a> {
+    type SolutionPart1 = Day06SolutionPart1;
+    type SolutionPart2 = Day06SolutionPart2;
+
+    fn description(&self) -> &'static str {
+        "Direct scan without grid"
+    }
+
+    fn new(input: &'a str) -> Day06Direct<'a> {
+        Day06Direct { input }
+    }
+
+    fn solution_part1(&self) -> Self::SolutionPart1 {
+        solution_part1(self.input).unwrap()
+    }
+
+    fn solution_part2(&self) -> Self::SolutionPart2 {
+        solution_part2(self.input, 10000).unwrap()
+    }
+}
+
+/// Index of the single position closest to `cell_pos`, or `None` on a tie.
+fn closest(positions: &[Position], cell_pos: &Position) -> Option<usize> {
+    let mut min_dist = i64::MAX;
+    let mut closest_id = None;
+    for (id, pos) in positions.iter().enumerate() {
+        let distance = manhattan_distance(pos, cell_pos);
+        if distance < min_dist {
+            min_dist = distance;
+            closest_id = Some(id);
+        } else if distance == min_dist {
+            closest_id = None;
+        }
+    }
+    closest_id
+}
+
+pub fn solution_part1(input: &str) -> Result<Day06SolutionPart1> {
+    let positions = parse_input(input)?;
+    let bounding_box = find_bounding_box(&positions)?;
+
+    let mut areas = vec![0; positions.len()];
+    // Any area touching the bounding box extends to infinity.
+    let mut infinite = vec![false; positions.len()];
+
+    for y in bounding_box.ymin..=bounding_box.ymax {
+        for x in bounding_box.xmin..=bounding_box.xmax {
+            if let Some(id) = closest(&positions, &Position { x, y }) {
+                areas[id] += 1;
+                if x == bounding_box.xmin
+                    || x == bounding_box.xmax
+                    || y == bounding_box.ymin
+                    || y == bounding_box.ymax
+                {
+                    infinite[id] = true;
+                }
+            }
+        }
+    }
+
+    areas
+        .iter()
+        .zip(infinite.iter())
+        .filter(|(_area, &infinite)| !infinite)
+        .map(|(&area, _infinite)| area)
+        .max()
+        .map(|area| area as Day06SolutionPart1)
+        .ok_or_else(|| "All areas are infinite".into())
+}
+
+pub fn solution_part2(input: &str, max_distance: i64) -> Result<Day06SolutionPart2> {
+    let positions = parse_input(input)?;
+    let bounding_box = find_bounding_box(&positions)?;
+
+    let mut count = 0;
+    for y in bounding_box.ymin..=bounding_box.ymax {
+        for x in bounding_box.xmin..=bounding_box.xmax {
+            let cell_pos = Position { x, y };
+            let distances_sum: i64 = positions
+                .iter()
+                .map(|pos| manhattan_distance(pos, &cell_pos))
+                .sum();
+            if distances_sum < max_distance {
+                count += 1;
+            }
+        }
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    mod part1 {
+        mod solution {
+            use super::super::super::Day06Direct;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 3290;
+                let to_check = Day06Direct::new(PUZZLE_INPUT).solution_part1();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day06Direct;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = 17;
+                let input = "1, 1
+                             1, 6
+                             8, 3
+                             3, 4
+                             5, 5
+                             8, 9";
+                let to_check = Day06Direct::new(input).solution_part1();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+    }
+
+    mod part2 {
+        mod solution {
+            use super::super::super::Day06Direct;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 45602;
+                let to_check = Day06Direct::new(PUZZLE_INPUT).solution_part2();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod given {
+            use super::super::super::solution_part2;
+            use crate::tests::init_logger;
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = 16;
+                let input = "1, 1
+                             1, 6
+                             8, 3
+                             3, 4
+                             5, 5
+                             8, 9";
+                let to_check = solution_part2(input, 32).unwrap();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+    }
+}
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use itertools::iproduct;
 use ndarray::Array2;
 
-use crate::{manhattan_distance, Position, Result};
+use crate::{find_bounding_box, manhattan_distance, BoundingBox, Position, Result};
 use crate::{parse_input, AoC, Day06SolutionPart1, Day06SolutionPart2};
 
 const SAFE_CELL_MARKER: CoordinateId = CoordinateId::max_value();
@@ -195,54 +195,6 @@ pub fn new_grid(bounding_box: &BoundingBox) -> (Grid, [i64; 2]) {
     )
 }
 
-#[derive(Debug, PartialEq)]
-pub struct BoundingBox {
-    xmin: i64,
-    xmax: i64,
-    ymin: i64,
-    ymax: i64,
-}
-
-impl BoundingBox {
-    pub fn width(&self) -> i64 {
-        self.xmax - self.xmin + 1
-    }
-    pub fn height(&self) -> i64 {
-        self.ymax - self.ymin + 1
-    }
-}
-
-fn find_bounding_box(input: &[Position]) -> Result<BoundingBox> {
-    let xmin = input
-        .iter()
-        .min_by_key(|pos| pos.x)
-        .ok_or_else(|| format!("Can't find minimum x value"))?
-        .x;
-    let xmax = input
-        .iter()
-        .max_by_key(|pos| pos.x)
-        .ok_or_else(|| format!("Can't find maximum x value"))?
-        .x;
-
-    let ymin = input
-        .iter()
-        .min_by_key(|pos| pos.y)
-        .ok_or_else(|| format!("Can't find minimum y value"))?
-        .y;
-    let ymax = input
-        .iter()
-        .max_by_key(|pos| pos.y)
-        .ok_or_else(|| format!("Can't find maximum y value"))?
-        .y;
-
-    Ok(BoundingBox {
-        xmin,
-        xmax,
-        ymin,
-        ymax,
-    })
-}
-
 #[cfg(test)]
 mod tests {
     mod part1 {
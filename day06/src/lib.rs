@@ -135,6 +135,7 @@ extern crate pretty_assertions;
 // #[macro_use]
 // extern crate log;
 extern crate itertools;
+#[cfg(feature = "ndarray")]
 #[macro_use]
 extern crate ndarray;
 
@@ -144,9 +145,14 @@ use std::error::Error;
 use std::result;
 use std::str::FromStr;
 
+#[cfg(feature = "ndarray")]
 pub mod initial;
+#[cfg(feature = "ndarray")]
 pub use initial::Day06Initial;
 
+pub mod direct;
+pub use direct::Day06Direct;
+
 type Day06SolutionPart1 = i64;
 type Day06SolutionPart2 = i64;
 
@@ -218,6 +224,54 @@ pub fn manhattan_distance(pos1: &Position, pos2: &Position) -> i64 {
     delta_x + delta_y
 }
 
+#[derive(Debug, PartialEq)]
+pub struct BoundingBox {
+    xmin: i64,
+    xmax: i64,
+    ymin: i64,
+    ymax: i64,
+}
+
+impl BoundingBox {
+    pub fn width(&self) -> i64 {
+        self.xmax - self.xmin + 1
+    }
+    pub fn height(&self) -> i64 {
+        self.ymax - self.ymin + 1
+    }
+}
+
+pub fn find_bounding_box(input: &[Position]) -> Result<BoundingBox> {
+    let xmin = input
+        .iter()
+        .min_by_key(|pos| pos.x)
+        .ok_or_else(|| format!("Can't find minimum x value"))?
+        .x;
+    let xmax = input
+        .iter()
+        .max_by_key(|pos| pos.x)
+        .ok_or_else(|| format!("Can't find maximum x value"))?
+        .x;
+
+    let ymin = input
+        .iter()
+        .min_by_key(|pos| pos.y)
+        .ok_or_else(|| format!("Can't find minimum y value"))?
+        .y;
+    let ymax = input
+        .iter()
+        .max_by_key(|pos| pos.y)
+        .ok_or_else(|| format!("Can't find maximum y value"))?
+        .y;
+
+    Ok(BoundingBox {
+        xmin,
+        xmax,
+        ymin,
+        ymax,
+    })
+}
+
 pub static PUZZLE_INPUT: &str = include_str!("../input");
 
 pub mod benchmark {
@@ -231,7 +285,11 @@ pub mod benchmark {
     >;
 
     pub fn to_benchmark<'a>() -> BenchmarkVector<'a> {
-        vec![Box::new(Day06Initial::new(PUZZLE_INPUT))]
+        vec![
+            Box::new(Day06Direct::new(PUZZLE_INPUT)),
+            #[cfg(feature = "ndarray")]
+            Box::new(Day06Initial::new(PUZZLE_INPUT)),
+        ]
     }
 }
 
@@ -0,0 +1,223 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{char_to_duration, parse_input, AoC, Day07SolutionPart1, Day07SolutionPart2, Result};
+
+use crate::{GraphNode, ParsedEdge};
+
+#[derive(Debug)]
+pub struct Day07Adjacency<'a> {
+    input: &'a str,
+}
+
+impl<'a> AoC<'a> for Day07Adjacency<'a> {
+    type SolutionPart1 = Day07SolutionPart1;
+    type SolutionPart2 = Day07SolutionPart2;
+
+    fn description(&self) -> &'static str {
+        "Adjacency map"
+    }
+
+    fn new(input: &'a str) -> Day07Adjacency<'a> {
+        Day07Adjacency { input }
+    }
+
+    fn solution_part1(&self) -> Self::SolutionPart1 {
+        solution_part1(self.input).unwrap()
+    }
+
+    fn solution_part2(&self) -> Self::SolutionPart2 {
+        let nb_workers = 5;
+        let step_baseline = 60;
+        solution_part2(self.input, nb_workers, step_baseline).unwrap()
+    }
+}
+
+/// Map every step to the steps it depends on.
+///
+/// Steps are kept sorted so that iterating over the map visits them in
+/// alphabetical order.
+type Dependencies = BTreeMap<GraphNode, BTreeSet<GraphNode>>;
+
+fn build_dependencies(input: impl Iterator<Item = ParsedEdge>) -> Dependencies {
+    let mut dependencies = Dependencies::new();
+    for parsed in input {
+        dependencies.entry(parsed.dependency).or_default();
+        dependencies
+            .entry(parsed.node)
+            .or_default()
+            .insert(parsed.dependency);
+    }
+    dependencies
+}
+
+/// First step, in alphabetical order, that is not `excluded` and whose
+/// dependencies are all `done`.
+fn next_ready(
+    dependencies: &Dependencies,
+    done: &BTreeSet<GraphNode>,
+    excluded: &BTreeSet<GraphNode>,
+) -> Option<GraphNode> {
+    dependencies
+        .iter()
+        .find(|(node, deps)| {
+            !done.contains(node) && !excluded.contains(node) && deps.is_subset(done)
+        })
+        .map(|(node, _deps)| *node)
+}
+
+fn solution_part1(input: &str) -> Result<Day07SolutionPart1> {
+    let dependencies = build_dependencies(parse_input(input));
+
+    let mut done: BTreeSet<GraphNode> = BTreeSet::new();
+    let mut solution = String::with_capacity(dependencies.len());
+
+    while let Some(node) = next_ready(&dependencies, &done, &BTreeSet::new()) {
+        done.insert(node);
+        solution.push(node);
+    }
+
+    if solution.len() != dependencies.len() {
+        return Err("steps contain a dependency cycle".into());
+    }
+
+    Ok(solution)
+}
+
+fn solution_part2(input: &str, nb_workers: usize, step_baseline: u8) -> Result<Day07SolutionPart2> {
+    let dependencies = build_dependencies(parse_input(input));
+
+    let mut done: BTreeSet<GraphNode> = BTreeSet::new();
+    let mut being_worked_on: BTreeSet<GraphNode> = BTreeSet::new();
+    // Time at which each worker's current step will be finished
+    let mut finishing: Vec<(i64, GraphNode)> = Vec::with_capacity(nb_workers);
+
+    let mut duration = 0;
+
+    while done.len() != dependencies.len() {
+        // Distribute work to the idle workers
+        while finishing.len() < nb_workers {
+            match next_ready(&dependencies, &done, &being_worked_on) {
+                Some(node) => {
+                    being_worked_on.insert(node);
+                    let step_duration = i64::from(char_to_duration(node, step_baseline));
+                    finishing.push((duration + step_duration, node));
+                }
+                None => break,
+            }
+        }
+
+        // Jump to the moment the next worker is done
+        duration = finishing
+            .iter()
+            .map(|(time, _node)| *time)
+            .min()
+            .ok_or_else(|| "steps contain a dependency cycle".to_string())?;
+        finishing.retain(|(time, node)| {
+            if *time == duration {
+                being_worked_on.remove(node);
+                done.insert(*node);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    Ok(duration)
+}
+
+#[cfg(test)]
+mod tests {
+
+    static EX_INPUT: &str = "Step C must be finished before step A can begin.
+                             Step C must be finished before step F can begin.
+                             Step A must be finished before step B can begin.
+                             Step A must be finished before step D can begin.
+                             Step B must be finished before step E can begin.
+                             Step D must be finished before step E can begin.
+                             Step F must be finished before step E can begin.";
+
+    mod part1 {
+
+        mod solution {
+            use super::super::super::Day07Adjacency;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let to_check = Day07Adjacency::new(PUZZLE_INPUT).solution_part1();
+
+                let expected = "EPWCFXKISTZVJHDGNABLQYMORU";
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod given {
+            use super::super::super::Day07Adjacency;
+            use super::super::EX_INPUT;
+            use crate::{tests::init_logger, AoC};
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let expected = "CABDFE";
+                let to_check = Day07Adjacency::new(EX_INPUT).solution_part1();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod extra {
+            use super::super::super::solution_part1;
+            use crate::tests::init_logger;
+
+            #[test]
+            fn cycle() {
+                init_logger();
+
+                let input = "Step A must be finished before step B can begin.
+                             Step B must be finished before step A can begin.";
+                assert!(solution_part1(input).is_err());
+            }
+        }
+    }
+
+    mod part2 {
+        mod solution {
+            use super::super::super::Day07Adjacency;
+            use crate::{tests::init_logger, AoC, PUZZLE_INPUT};
+
+            #[test]
+            fn solution() {
+                init_logger();
+
+                let expected = 952;
+                let to_check = Day07Adjacency::new(PUZZLE_INPUT).solution_part2();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+
+        mod given {
+            use super::super::super::solution_part2;
+            use super::super::EX_INPUT;
+            use crate::tests::init_logger;
+
+            #[test]
+            fn ex01() {
+                init_logger();
+
+                let nb_workers = 2;
+                let step_baseline = 0;
+                let expected = 15;
+
+                let to_check = solution_part2(EX_INPUT, nb_workers, step_baseline).unwrap();
+
+                assert_eq!(to_check, expected);
+            }
+        }
+    }
+}
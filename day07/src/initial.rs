@@ -5,7 +5,7 @@ use petgraph::visit::IntoNodeReferences;
 
 use crate::{parse_input, AoC, Day07SolutionPart1, Day07SolutionPart2, Result};
 
-use crate::{build_graph, Graph, GraphIdx, GraphNode};
+use crate::{build_graph, char_to_duration, Graph, GraphIdx, GraphNode};
 
 #[derive(Debug)]
 pub struct Day07Initial<'a> {
@@ -111,10 +111,6 @@ fn find_nodes_ready(
     });
 }
 
-fn char_to_duration(c: char, step_baseline: u8) -> u8 {
-    (c as u8 - b'A') + step_baseline + 1
-}
-
 #[derive(Debug, PartialEq)]
 pub struct Busy {
     steps_remaining: u8,
@@ -18,17 +18,25 @@
 // #[macro_use]
 // extern crate log;
 
+#[cfg(feature = "graph")]
 use std::collections::HashMap;
 use std::fmt::Debug;
 
+#[cfg(feature = "graph")]
 pub mod initial;
+#[cfg(feature = "graph")]
 pub use crate::initial::Day07Initial;
 
+pub mod adjacency;
+pub use crate::adjacency::Day07Adjacency;
+
 pub type Result<T> = std::result::Result<T, Box<std::error::Error>>;
 
 pub type GraphNode = char;
 pub type GraphEdge = ();
+#[cfg(feature = "graph")]
 pub type GraphIdx = petgraph::graph::NodeIndex;
+#[cfg(feature = "graph")]
 pub type Graph = petgraph::Graph<GraphNode, GraphEdge, petgraph::Directed>;
 
 type Day07SolutionPart1 = String;
@@ -74,6 +82,7 @@ pub fn parse_input<'a>(input: &'a str) -> impl Iterator<Item = ParsedEdge> + 'a
     })
 }
 
+#[cfg(feature = "graph")]
 pub fn build_graph(input: impl Iterator<Item = ParsedEdge>) -> Graph {
     let mut graph = Graph::new();
 
@@ -105,11 +114,16 @@ pub fn build_graph(input: impl Iterator<Item = ParsedEdge>) -> Graph {
     graph
 }
 
+#[cfg(feature = "graph")]
 pub fn graph_to_dot(graph: &Graph) -> String {
     let dot = petgraph::dot::Dot::with_config(&graph, &[petgraph::dot::Config::EdgeNoLabel]);
     format!("{:?}", dot)
 }
 
+fn char_to_duration(c: char, step_baseline: u8) -> u8 {
+    (c as u8 - b'A') + step_baseline + 1
+}
+
 pub static PUZZLE_INPUT: &str = include_str!("../input");
 
 pub mod benchmark {
@@ -123,7 +137,11 @@ pub mod benchmark {
     >;
 
     pub fn to_benchmark<'a>() -> BenchmarkVector<'a> {
-        vec![Box::new(Day07Initial::new(PUZZLE_INPUT))]
+        vec![
+            Box::new(Day07Adjacency::new(PUZZLE_INPUT)),
+            #[cfg(feature = "graph")]
+            Box::new(Day07Initial::new(PUZZLE_INPUT)),
+        ]
     }
 }
 
@@ -132,8 +150,7 @@ mod tests {
     use env_logger;
     use std::env;
 
-    use crate::parse_input;
-    use crate::{build_graph, graph_to_dot, ParsedEdge};
+    use crate::{parse_input, ParsedEdge};
 
     pub fn init_logger() {
         env::var("RUST_LOG")
@@ -193,8 +210,11 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "graph")]
     #[test]
     fn parse_and_build_graph() {
+        use crate::{build_graph, graph_to_dot};
+
         let input = "Step C must be finished before step A can begin.
                      Step C must be finished before step F can begin.
                      Step A must be finished before step B can begin.
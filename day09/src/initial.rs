@@ -416,6 +416,7 @@ mod tests {
         mod extra {
             use super::super::super::{parse_input, DoubleLinkedList, Game, Idx, Input};
             use crate::tests::init_logger;
+            use snapshot::assert_snapshot;
 
             #[test]
             fn double_linked_list() {
@@ -524,6 +525,26 @@ mod tests {
                     .for_each(|(to_check, expected)| assert_eq!(to_check, expected));
             }
 
+            #[test]
+            fn ex00_steps_states_snapshot() {
+                init_logger();
+
+                let input = "9 players; last marble is worth 25 points";
+                let Input {
+                    nb_players,
+                    last_marble_points,
+                } = parse_input(input).unwrap();
+
+                let mut game = Game::new(nb_players, last_marble_points);
+                let mut states = vec![game.state().trim_end().to_string()];
+                for _ in 0..last_marble_points {
+                    game.next_player_step();
+                    states.push(game.state().trim_end().to_string());
+                }
+
+                assert_snapshot!("ex00_states", states.join("\n"));
+            }
+
         }
     }
 
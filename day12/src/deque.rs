@@ -0,0 +1,58 @@
+//! `Vec`-backed stand-in for `slice_deque::SliceDeque`, used when the
+//! `slice-deque` feature is disabled.
+//!
+//! Only the operations needed by the solutions are provided. Pushing at the
+//! front is linear in the length, which is fine for the few pots added per
+//! generation.
+
+use std::iter::FromIterator;
+use std::ops::{Deref, DerefMut};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SliceDeque<T>(Vec<T>);
+
+impl<T> SliceDeque<T> {
+    #[allow(dead_code)]
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        self.0.insert(0, value);
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        self.0.push(value);
+    }
+
+    /// Shorten the deque to `len` elements by removing from the front.
+    pub fn truncate_front(&mut self, len: usize) {
+        let excess = self.0.len().saturating_sub(len);
+        self.0.drain(..excess);
+    }
+
+    /// Shorten the deque to `len` elements by removing from the back.
+    pub fn truncate_back(&mut self, len: usize) {
+        self.0.truncate(len);
+    }
+}
+
+impl<T> Deref for SliceDeque<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for SliceDeque<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T> FromIterator<T> for SliceDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        SliceDeque(iter.into_iter().collect())
+    }
+}
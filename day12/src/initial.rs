@@ -1,4 +1,7 @@
 // use std::collections::VecDeque;
+#[cfg(not(feature = "slice-deque"))]
+use crate::deque::SliceDeque;
+#[cfg(feature = "slice-deque")]
 use slice_deque::SliceDeque;
 
 use crate::{pot_slice_to_string, Input, Note, PotState, Result};
@@ -198,6 +201,7 @@ mod tests {
         mod given {
             use super::super::super::*;
             use crate::{tests::init_logger, AoC};
+            use snapshot::assert_snapshot;
 
             #[test]
             fn ex00_steps() {
@@ -281,6 +285,62 @@ mod tests {
                 assert_eq!(i0, -5);
             }
 
+            #[test]
+            fn ex00_steps_snapshot() {
+                init_logger();
+
+                let input = "initial state: #..#.#..##......###...###
+
+                             ...## => #
+                             ..#.. => #
+                             .#... => #
+                             .#.#. => #
+                             .#.## => #
+                             .##.. => #
+                             .#### => #
+                             #.#.# => #
+                             #.### => #
+                             ##.#. => #
+                             ##.## => #
+                             ###.. => #
+                             ###.# => #
+                             ####. => #";
+                let parsed_input = Input::new(input).unwrap();
+                let notes: Vec<Note> = parsed_input.iter_notes().collect();
+
+                let mut state: SliceDeque<PotState> =
+                    parsed_input.initial_state.state.iter().cloned().collect();
+                for _ in 0..3 {
+                    state.push_front(PotState::NoPlant);
+                    state.push_back(PotState::NoPlant);
+                }
+                let mut i0 = -3;
+                let mut next_state = state.clone();
+
+                // Render the same window as the puzzle's example, pots -3 to 35.
+                let render = |generation: usize, state: &[PotState], i0: i64| {
+                    let window: Vec<PotState> = (-3..=35)
+                        .map(|pot| {
+                            let idx = pot - i0;
+                            if idx >= 0 && (idx as usize) < state.len() {
+                                state[idx as usize]
+                            } else {
+                                PotState::NoPlant
+                            }
+                        })
+                        .collect();
+                    format!("{:2}: {}", generation, pot_slice_to_string(&window))
+                };
+
+                let mut states = vec![render(0, state.as_slice(), i0)];
+                for generation in 1..=20 {
+                    part1_step(&mut state, &mut next_state, &notes, &mut i0);
+                    states.push(render(generation, state.as_slice(), i0));
+                }
+
+                assert_snapshot!("ex00_states", states.join("\n"));
+            }
+
             #[test]
             fn ex00() {
                 init_logger();
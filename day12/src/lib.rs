@@ -136,6 +136,9 @@ extern crate pretty_assertions;
 pub mod initial;
 pub use crate::initial::Day12Initial;
 
+#[cfg(not(feature = "slice-deque"))]
+mod deque;
+
 type Result<T> = std::result::Result<T, Box<Error>>;
 
 macro_rules! err {
@@ -0,0 +1,177 @@
+//! # Golden-file snapshots
+//!
+//! Helpers to compare rendered text (for example the successive states of a
+//! simulation) against a golden file stored under the calling crate's
+//! `snapshots/` directory.
+//!
+//! On a normal run, a missing or different golden file fails the test and
+//! prints a line-by-line diff. To (re)record the golden files, run the tests
+//! with the `UPDATE_SNAPSHOTS` environment variable set:
+//!
+//! ```text
+//! UPDATE_SNAPSHOTS=1 cargo test
+//! ```
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable switching from comparing to recording golden files.
+pub const UPDATE_ENV_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// Directory, relative to the crate root, holding the golden files.
+pub const SNAPSHOTS_DIR: &str = "snapshots";
+
+/// Compare `$actual` against the golden file `snapshots/$name.txt` of the
+/// crate invoking the macro.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr) => {
+        $crate::assert_snapshot(env!("CARGO_MANIFEST_DIR"), $name, &$actual)
+    };
+}
+
+/// Outcome of checking a rendered text against its golden file.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// Golden file exists and matches.
+    Matched,
+    /// Golden file was (re)written because update mode is enabled.
+    Updated,
+    /// Golden file does not exist.
+    Missing,
+    /// Golden file differs; contains the rendered diff.
+    Mismatch(String),
+}
+
+/// Path of the golden file `name` for the crate at `crate_dir`.
+pub fn snapshot_path(crate_dir: impl AsRef<Path>, name: &str) -> PathBuf {
+    crate_dir
+        .as_ref()
+        .join(SNAPSHOTS_DIR)
+        .join(format!("{}.txt", name))
+}
+
+/// `true` when the `UPDATE_SNAPSHOTS` environment variable asks for recording.
+pub fn update_mode() -> bool {
+    match env::var(UPDATE_ENV_VAR) {
+        Ok(value) => !value.is_empty() && value != "0",
+        Err(_) => false,
+    }
+}
+
+/// Check `actual` against the golden file at `path`, writing it instead
+/// when `update` is `true`.
+pub fn check(path: &Path, actual: &str, update: bool) -> std::io::Result<Outcome> {
+    let actual = normalize(actual);
+
+    if update {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, &actual)?;
+        return Ok(Outcome::Updated);
+    }
+
+    if !path.exists() {
+        return Ok(Outcome::Missing);
+    }
+
+    let expected = normalize(&fs::read_to_string(path)?);
+    if expected == actual {
+        Ok(Outcome::Matched)
+    } else {
+        Ok(Outcome::Mismatch(diff(&expected, &actual)))
+    }
+}
+
+/// Panic unless `actual` matches the golden file `name` of the crate at
+/// `crate_dir`. Prefer the [`assert_snapshot!`] macro which fills in the
+/// crate directory.
+#[track_caller]
+pub fn assert_snapshot(crate_dir: impl AsRef<Path>, name: &str, actual: &str) {
+    let path = snapshot_path(crate_dir, name);
+    match check(&path, actual, update_mode()) {
+        Ok(Outcome::Matched) | Ok(Outcome::Updated) => {}
+        Ok(Outcome::Missing) => panic!(
+            "Snapshot '{}' not found at {}.\nRun with {}=1 to record it.",
+            name,
+            path.display(),
+            UPDATE_ENV_VAR
+        ),
+        Ok(Outcome::Mismatch(diff)) => panic!(
+            "Snapshot '{}' does not match {}:\n{}\nRun with {}=1 to accept the new output.",
+            name,
+            path.display(),
+            diff,
+            UPDATE_ENV_VAR
+        ),
+        Err(e) => panic!("Failed to access snapshot {}: {}", path.display(), e),
+    }
+}
+
+/// Use `\n` line endings and exactly one trailing newline.
+fn normalize(text: &str) -> String {
+    let mut normalized = text.replace("\r\n", "\n").trim_end_matches('\n').to_string();
+    normalized.push('\n');
+    normalized
+}
+
+/// Line-by-line diff, listing only the lines that differ.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    let mut out = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        let left = expected.get(i);
+        let right = actual.get(i);
+        if left == right {
+            continue;
+        }
+        out.push_str(&format!("line {}:\n", i + 1));
+        if let Some(left) = left {
+            out.push_str(&format!("  - {}\n", left));
+        }
+        if let Some(right) = right {
+            out.push_str(&format!("  + {}\n", right));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::{check, diff, Outcome};
+
+    fn temp_snapshot(name: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("snapshot-tests-{}", std::process::id()))
+            .join(format!("{}.txt", name));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn record_then_compare() {
+        let path = temp_snapshot("record_then_compare");
+
+        assert_eq!(check(&path, "a\nb", false).unwrap(), Outcome::Missing);
+        assert_eq!(check(&path, "a\nb", true).unwrap(), Outcome::Updated);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a\nb\n");
+        assert_eq!(check(&path, "a\r\nb\n", false).unwrap(), Outcome::Matched);
+        assert_eq!(
+            check(&path, "a\nc", false).unwrap(),
+            Outcome::Mismatch("line 2:\n  - b\n  + c\n".to_string())
+        );
+    }
+
+    #[test]
+    fn diff_different_lengths() {
+        assert_eq!(diff("a\nb\n", "a\n"), "line 2:\n  - b\n");
+        assert_eq!(diff("a\n", "a\nb\n"), "line 2:\n  + b\n");
+    }
+}